@@ -0,0 +1,47 @@
+// Benchmarks of every day on its full input: `cargo bench -p aoc [-- dayNN]`.
+// Alternative implementations of a part are benchmarked in the same group
+// as the main one, so criterion reports them side by side. Days that are not
+// solved yet are left out.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::Path;
+use utils::{Input, Solution, Source};
+
+fn bench_day<S: Solution>(c: &mut Criterion, solution: &S, input: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(input);
+    let input = Input::read(&Source::File(path), solution.read_options()).unwrap();
+    let parsed = solution.parse(&input).unwrap();
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    group.bench_function("parse", |b| b.iter(|| solution.parse(&input).unwrap()));
+    group.bench_function("part one", |b| {
+        b.iter(|| solution.part_one(&parsed).unwrap())
+    });
+    group.bench_function("part two", |b| {
+        b.iter(|| solution.part_two(&parsed).unwrap())
+    });
+    for alt in solution.alternatives() {
+        let name = format!("part {} ({})", alt.part, alt.name);
+        group.bench_function(name, |b| b.iter(|| (alt.solve)(&parsed).unwrap()));
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day(c, &day01::Day01, "day01/testdata/input.txt");
+    bench_day(c, &day02::Day02, "day02/testdata/input.txt");
+    bench_day(c, &day03::Day03, "day03/testdata/input.txt");
+    bench_day(c, &day04::Day04, "day04/testdata/input.txt");
+    bench_day(c, &day05::Day05, "day05/testdata/input.txt");
+    bench_day(c, &day06::Day06, "day06/input");
+    bench_day(c, &day10::Day10, "day10/input.txt");
+    bench_day(c, &day17::Day17, "day17/input.txt");
+    bench_day(c, &day18::Day18, "day18/input.txt");
+    bench_day(c, &day19::Day19, "day19/input.txt");
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = days
+}
+criterion_main!(benches);
@@ -0,0 +1,140 @@
+//! Single entry point for all the days:
+//! `aoc run <day> [--part 1|2|both] [--input PATH|-] [--time] [--runs N] [--format text|json]`.
+
+pub mod regression;
+
+use utils::cli::{self, Format};
+use utils::{Part, Runner, Source};
+
+/// Days that have a solution, in order.
+pub const DAYS: [u8; 12] = [1, 2, 3, 4, 5, 6, 7, 10, 11, 17, 18, 19];
+
+/// Returns the solution of the given day, if there is one.
+pub fn solution(day: u8) -> Option<&'static dyn Runner> {
+    let runner: &'static dyn Runner = match day {
+        1 => &day01::Day01,
+        2 => &day02::Day02,
+        3 => &day03::Day03,
+        4 => &day04::Day04,
+        5 => &day05::Day05,
+        6 => &day06::Day06,
+        7 => &day07::Day07,
+        10 => &day10::Day10,
+        11 => &day11::Day11,
+        17 => &day17::Day17,
+        18 => &day18::Day18,
+        19 => &day19::Day19,
+        _ => return None,
+    };
+    Some(runner)
+}
+
+/// Parses a day given as `4`, `04` or `day04`.
+pub fn parse_day(arg: &str) -> Option<u8> {
+    let num = arg.strip_prefix("day").unwrap_or(arg);
+    match num.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Some(day),
+        _ => None,
+    }
+}
+
+/// Arguments of `aoc run`.
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub day: u8,
+    pub parts: Vec<Part>,
+    pub source: Source,
+    /// Number of timed runs, `None` unless `--time` is given.
+    pub time: Option<usize>,
+    pub format: Format,
+}
+
+/// Parses the arguments following `run`; the input defaults to stdin.
+pub fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut day = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut source = Source::Stdin;
+    let mut time = None;
+    let mut format = Format::Text;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = cli::option_value("--part", &mut it)?;
+                parts = Part::parse_arg(value)
+                    .ok_or_else(|| format!("invalid part '{}', expected 1, 2 or both", value))?;
+            }
+            "--input" | "-i" => source = Source::from_arg(cli::option_value("--input", &mut it)?),
+            "--time" => {
+                time.get_or_insert(cli::DEFAULT_RUNS);
+            }
+            "--runs" => time = Some(cli::positive_arg(arg, cli::option_value(arg, &mut it)?)?),
+            "--format" => format = Format::parse_arg(cli::option_value(arg, &mut it)?)?,
+            opt if opt.starts_with("--") => return Err(format!("unknown option '{}'", opt)),
+            value if day.is_none() => {
+                day = Some(parse_day(value).ok_or_else(|| format!("invalid day '{}'", value))?);
+            }
+            value => return Err(format!("unexpected argument '{}'", value)),
+        }
+    }
+    let day = day.ok_or("missing day")?;
+    Ok(RunArgs {
+        day,
+        parts,
+        source,
+        time,
+        format,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("4"), Some(4));
+        assert_eq!(parse_day("04"), Some(4));
+        assert_eq!(parse_day("day17"), Some(17));
+        assert_eq!(parse_day("26"), None);
+        assert_eq!(parse_day("dayx"), None);
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        assert_eq!(
+            parse_run_args(&args("day04 --part 2 --input input.txt")),
+            Ok(RunArgs {
+                day: 4,
+                parts: vec![Part::Two],
+                source: Source::from_arg("input.txt"),
+                time: None,
+                format: Format::Text,
+            })
+        );
+        let run = parse_run_args(&args("1")).unwrap();
+        assert_eq!(run.parts, vec![Part::One, Part::Two]);
+        assert_eq!(run.source, Source::Stdin);
+        assert_eq!(run.time, None);
+        let run = parse_run_args(&args("17 --runs 3 --time")).unwrap();
+        assert_eq!(run.time, Some(3));
+        let run = parse_run_args(&args("5 --format json")).unwrap();
+        assert_eq!(run.format, Format::Json);
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("1 --part 3")).is_err());
+        assert!(parse_run_args(&args("1 --input")).is_err());
+        assert!(parse_run_args(&args("1 2")).is_err());
+    }
+
+    #[test]
+    fn test_registry() {
+        for &day in DAYS.iter() {
+            assert_eq!(solution(day).map(|s| s.day()), Some(day));
+        }
+        assert!(solution(8).is_none());
+    }
+}
@@ -0,0 +1,57 @@
+use aoc::regression;
+use std::path::Path;
+use std::{env, process};
+use utils::cli;
+
+const USAGE: &str = "usage: aoc run <day> [--part 1|2|both] [--input PATH|-]
+                     [--time] [--runs N] [--format text|json]
+       aoc check [MANIFEST]
+       aoc list";
+
+// check the answers listed in the manifest, printing a table of the results
+fn check(manifest: &str) -> bool {
+    let path = Path::new(manifest);
+    let entries = regression::read_manifest(path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let outcomes = regression::check(dir, &entries);
+    print!("{}", regression::table(&outcomes));
+    let failed = outcomes.iter().filter(|o| !o.passed()).count();
+    println!("{} checked, {} mismatches", outcomes.len(), failed);
+    failed == 0
+}
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => {
+            let run = aoc::parse_run_args(&args[1..]).unwrap_or_else(|msg| usage_error(&msg));
+            let solution = aoc::solution(run.day)
+                .unwrap_or_else(|| usage_error(&format!("no solution for day {}", run.day)));
+            if !cli::execute(solution, &run.source, &run.parts, run.time, run.format) {
+                process::exit(1);
+            }
+        }
+        Some("check") => {
+            let manifest = args.get(1).map_or("answers.txt", String::as_str);
+            if !check(manifest) {
+                process::exit(1);
+            }
+        }
+        Some("list") => {
+            for day in aoc::DAYS.iter() {
+                println!("day{:02}", day);
+            }
+        }
+        Some("help") | Some("--help") | Some("-h") => println!("{}", USAGE),
+        Some(cmd) => usage_error(&format!("unknown command '{}'", cmd)),
+        None => usage_error("missing command"),
+    }
+}
@@ -0,0 +1,305 @@
+//! Regression check of the answers listed in a manifest file against what
+//! the solvers produce for the checked-in inputs.
+
+use crate::solution;
+use std::path::{Path, PathBuf};
+use utils::input::tokens;
+use utils::{cli, Error, Input, Part, ReadOptions, Result, Source, Trim};
+
+/// What a manifest entry expects from the solver.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expectation {
+    Answer(String),
+    /// The solver must reject the input.
+    Error,
+}
+
+/// One manifest entry: the expected result of `part` of `day` on `input`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    /// Path as written in the manifest, relative to the manifest directory.
+    pub input: String,
+    pub expected: Expectation,
+}
+
+/// Parses a manifest: one `day part input answer` entry per line, blank
+/// lines and `#` comments are ignored.
+pub fn parse_manifest(input: &Input) -> Result<Vec<Expected>> {
+    let mut entries = Vec::new();
+    for line in input.lines() {
+        if line.text.starts_with('#') {
+            continue;
+        }
+        let entry = parse_entry(&line.text).map_err(|e| e.at_line(line.number))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_entry(text: &str) -> Result<Expected> {
+    let toks: Vec<(usize, &str)> = tokens(text).collect();
+    if toks.len() != 4 {
+        return Err(Error::parse(format!(
+            "expected 'day part input answer', found {} fields",
+            toks.len()
+        ))
+        .at_column(1));
+    }
+    let day = crate::parse_day(toks[0].1)
+        .ok_or_else(|| Error::parse(format!("invalid day '{}'", toks[0].1)).at_column(toks[0].0))?;
+    let part = match toks[1].1 {
+        "1" => Part::One,
+        "2" => Part::Two,
+        p => return Err(Error::parse(format!("invalid part '{}'", p)).at_column(toks[1].0)),
+    };
+    let expected = match toks[3].1 {
+        "error" => Expectation::Error,
+        answer => Expectation::Answer(answer.to_string()),
+    };
+    Ok(Expected {
+        day,
+        part,
+        input: toks[2].1.to_string(),
+        expected,
+    })
+}
+
+/// Reads and parses the manifest file.
+pub fn read_manifest(path: &Path) -> Result<Vec<Expected>> {
+    let options = ReadOptions::default().trim(Trim::Both).skip_empty();
+    let input = Input::read(&Source::File(path.to_path_buf()), options)?;
+    parse_manifest(&input).map_err(|e| e.in_file(input.name()))
+}
+
+/// Why the solver gave no answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    /// Whether the solver rejected the input as malformed or invalid, as
+    /// opposed to failing to read it or finding no answer.
+    pub rejected: bool,
+    pub message: String,
+}
+
+impl From<&Error> for Failure {
+    fn from(err: &Error) -> Failure {
+        Failure {
+            rejected: matches!(err, Error::Parse { .. } | Error::Validation { .. }),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// The result of checking one manifest entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub entry: Expected,
+    /// The answer, or why the solver failed.
+    pub actual: std::result::Result<String, Failure>,
+}
+
+impl Outcome {
+    /// Whether the solver gave the expected answer or, for an `error` entry,
+    /// rejected the input; an input that cannot be read never passes.
+    pub fn passed(&self) -> bool {
+        match (&self.entry.expected, &self.actual) {
+            (Expectation::Answer(want), Ok(got)) => want == got,
+            (Expectation::Error, Err(failure)) => failure.rejected,
+            _ => false,
+        }
+    }
+}
+
+/// Runs every entry of the manifest, input paths being relative to `dir`.
+/// Every entry is solved on its own, so that the failure of a part does not
+/// hide the answer of the other.
+pub fn check(dir: &Path, entries: &[Expected]) -> Vec<Outcome> {
+    entries
+        .iter()
+        .map(|entry| {
+            let source = Source::File(PathBuf::from(dir).join(&entry.input));
+            let result = match solution(entry.day) {
+                Some(runner) => cli::run_source(runner, &source, &[entry.part], None),
+                None => Err(Error::no_solution(format!(
+                    "no solver for day {}",
+                    entry.day
+                ))),
+            };
+            let actual = match result {
+                Ok(report) => Ok(report.results[0].answer.clone()),
+                Err(err) => Err(Failure::from(&err)),
+            };
+            Outcome {
+                entry: entry.clone(),
+                actual,
+            }
+        })
+        .collect()
+}
+
+/// Formats the outcomes as an aligned table with one row per entry; the
+/// error message of a failed solver goes in the last column.
+pub fn table(outcomes: &[Outcome]) -> String {
+    let mut rows = vec![[
+        "day".to_string(),
+        "part".to_string(),
+        "input".to_string(),
+        "expected".to_string(),
+        "actual".to_string(),
+        "status".to_string(),
+        "error".to_string(),
+    ]];
+    for outcome in outcomes.iter() {
+        let entry = &outcome.entry;
+        let expected = match &entry.expected {
+            Expectation::Answer(answer) => answer.clone(),
+            Expectation::Error => "error".to_string(),
+        };
+        let (actual, message) = match &outcome.actual {
+            Ok(answer) => (answer.clone(), String::new()),
+            Err(failure) => ("error".to_string(), failure.message.clone()),
+        };
+        let status = if outcome.passed() { "ok" } else { "MISMATCH" };
+        rows.push([
+            entry.day.to_string(),
+            entry.part.number().to_string(),
+            entry.input.clone(),
+            expected,
+            actual,
+            status.to_string(),
+            message,
+        ]);
+    }
+    let mut widths = [0; 7];
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    for row in rows.iter() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &w)| format!("{:w$}", cell, w = w))
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = "# comment\n4 2 day04/testdata/valid.txt 4\n\n03 1 empty.txt error\n";
+        let options = ReadOptions::default().trim(Trim::Both).skip_empty();
+        let entries = parse_manifest(&Input::from_text(text, options)).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                Expected {
+                    day: 4,
+                    part: Part::Two,
+                    input: "day04/testdata/valid.txt".to_string(),
+                    expected: Expectation::Answer("4".to_string()),
+                },
+                Expected {
+                    day: 3,
+                    part: Part::One,
+                    input: "empty.txt".to_string(),
+                    expected: Expectation::Error,
+                },
+            ]
+        );
+        let err = parse_manifest(&Input::from_text("4 3 x 1\n", options)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: parse error: invalid part '3'"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let entry = Expected {
+            day: 5,
+            part: Part::One,
+            input: "in.txt".to_string(),
+            expected: Expectation::Answer("855".to_string()),
+        };
+        let outcomes = vec![
+            Outcome {
+                entry: entry.clone(),
+                actual: Ok("855".to_string()),
+            },
+            Outcome {
+                entry,
+                actual: Ok("854".to_string()),
+            },
+        ];
+        assert!(outcomes[0].passed());
+        assert!(!outcomes[1].passed());
+        assert_eq!(
+            table(&outcomes),
+            "day  part  input   expected  actual  status    error\n\
+             5    1     in.txt  855       855     ok\n\
+             5    1     in.txt  855       854     MISMATCH\n"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let entry = |day, part, input: &str, expected| Expected {
+            day,
+            part,
+            input: input.to_string(),
+            expected,
+        };
+        let single = "day05/testdata/single.txt";
+        let entries = vec![
+            // the last seat of the aircraft has a highest id but no free seat after it
+            entry(
+                5,
+                Part::One,
+                single,
+                Expectation::Answer("1023".to_string()),
+            ),
+            entry(5, Part::Two, single, Expectation::Error),
+            entry(
+                5,
+                Part::One,
+                "day05/testdata/missing.txt",
+                Expectation::Error,
+            ),
+            entry(
+                3,
+                Part::One,
+                "day03/testdata/invalid01.txt",
+                Expectation::Error,
+            ),
+        ];
+        let outcomes = check(&dir, &entries);
+        let passed: Vec<bool> = outcomes.iter().map(|o| o.passed()).collect();
+        assert_eq!(passed, vec![true, false, false, true]);
+        let failure = |i: usize| outcomes[i].actual.clone().unwrap_err();
+        assert!(failure(1).message.starts_with("no solution"));
+        assert!(!failure(2).rejected);
+    }
+
+    // every solver against every checked-in input listed in answers.txt
+    #[test]
+    fn test_manifest() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let entries = read_manifest(&dir.join("answers.txt")).unwrap();
+        let failed: Vec<Outcome> = check(&dir, &entries)
+            .into_iter()
+            .filter(|o| !o.passed())
+            .collect();
+        assert!(failed.is_empty(), "answer mismatches:\n{}", table(&failed));
+    }
+}
@@ -0,0 +1,405 @@
+use num_bigint::BigInt;
+use std::fmt;
+use std::ops::{Add, Range, Sub};
+use utils::{Error, Result};
+
+/// Entry types the solver works on. Sums are computed in the wider `Sum`
+/// type, so adding up k entries cannot overflow.
+pub trait Number: Clone + Ord + fmt::Debug + fmt::Display {
+    type Sum: Clone + Ord + Default + Add<Output = Self::Sum> + Sub<Output = Self::Sum>;
+
+    fn widen(&self) -> Self::Sum;
+
+    fn to_bigint(&self) -> BigInt;
+}
+
+impl Number for u32 {
+    type Sum = i64;
+
+    fn widen(&self) -> i64 {
+        *self as i64
+    }
+
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(*self)
+    }
+}
+
+impl Number for i64 {
+    type Sum = i128;
+
+    fn widen(&self) -> i128 {
+        *self as i128
+    }
+
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(*self)
+    }
+}
+
+impl Number for BigInt {
+    type Sum = BigInt;
+
+    fn widen(&self) -> BigInt {
+        self.clone()
+    }
+
+    fn to_bigint(&self) -> BigInt {
+        self.clone()
+    }
+}
+
+/// Entries of the expense report summing to the target, in input order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Combination<T> {
+    /// 0-based positions of the entries in the input.
+    pub indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+impl<T: Number> Combination<T> {
+    /// Product of the values, computed without overflow.
+    pub fn product(&self) -> BigInt {
+        self.values
+            .iter()
+            .fold(BigInt::from(1), |acc, v| acc * v.to_bigint())
+    }
+}
+
+// the entries sorted by value, with their positions in the input
+fn sort_entries<T: Number>(values: &[T]) -> Vec<(T, usize)> {
+    let mut sorted: Vec<(T, usize)> = values.iter().cloned().zip(0..).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+// whether no entry is negative, in which case the search can stop as soon
+// as an entry exceeds what is left of the target
+fn non_negative<T: Number>(sorted: &[(T, usize)]) -> bool {
+    sorted
+        .first()
+        .is_none_or(|(v, _)| v.widen() >= T::Sum::default())
+}
+
+/// Finds `k` distinct entries (by position) summing to `target`.
+///
+/// The entries are sorted once, then the last two are found with two
+/// pointers, so the search takes O(n^(k-1)) time for k >= 2.
+pub fn find_sum<T: Number>(values: &[T], target: T, k: usize) -> Result<Combination<T>> {
+    let sorted = sort_entries(values);
+    let prune = non_negative(&sorted);
+    let mut chosen = Vec::with_capacity(k);
+    if !find_k(&sorted, 0, k, target.widen(), prune, &mut chosen) {
+        return Err(Error::no_solution(format!(
+            "no {} entries summing to {} found, please check the input!",
+            k, target
+        )));
+    }
+    let mut indices: Vec<usize> = chosen.iter().map(|&pos| sorted[pos].1).collect();
+    indices.sort_unstable();
+    Ok(Combination {
+        values: indices.iter().map(|&i| values[i].clone()).collect(),
+        indices,
+    })
+}
+
+// find_k looks for k entries of sorted[start..] summing to target, pushing their
+// positions in `sorted` onto `chosen`.
+fn find_k<T: Number>(
+    sorted: &[(T, usize)],
+    start: usize,
+    k: usize,
+    target: T::Sum,
+    prune: bool,
+    chosen: &mut Vec<usize>,
+) -> bool {
+    match k {
+        0 => target == T::Sum::default(),
+        1 => match sorted[start..].binary_search_by(|(v, _)| v.widen().cmp(&target)) {
+            Ok(pos) => {
+                chosen.push(start + pos);
+                true
+            }
+            Err(_) => false,
+        },
+        2 => {
+            if sorted.len() < start + 2 {
+                return false;
+            }
+            let (mut lo, mut hi) = (start, sorted.len() - 1);
+            while lo < hi {
+                let sum = sorted[lo].0.widen() + sorted[hi].0.widen();
+                match sum.cmp(&target) {
+                    std::cmp::Ordering::Equal => {
+                        chosen.push(lo);
+                        chosen.push(hi);
+                        return true;
+                    }
+                    std::cmp::Ordering::Less => lo += 1,
+                    std::cmp::Ordering::Greater => hi -= 1,
+                }
+            }
+            false
+        }
+        _ => {
+            for i in start..sorted.len() {
+                let value = sorted[i].0.widen();
+                if prune && value > target {
+                    break;
+                }
+                // the same value at the next position cannot give a new result
+                if i > start && sorted[i].0 == sorted[i - 1].0 {
+                    continue;
+                }
+                chosen.push(i);
+                if find_k(sorted, i + 1, k - 1, target.clone() - value, prune, chosen) {
+                    return true;
+                }
+                chosen.pop();
+            }
+            false
+        }
+    }
+}
+
+/// Iterator over every combination of `k` distinct entries (by position)
+/// summing to the target, see [`combinations`].
+pub struct Combinations<T: Number> {
+    sorted: Vec<(T, usize)>,
+    k: usize,
+    target: T::Sum,
+    prune: bool,
+    // positions in `sorted` of the first k-1 entries of the current combinations
+    prefix: Vec<usize>,
+    // positions in `sorted` left for the last entry
+    last: Range<usize>,
+    started: bool,
+}
+
+/// Returns an iterator over all the combinations of `k` entries summing to
+/// `target`. Equal values at different positions give different combinations.
+pub fn combinations<T: Number>(values: &[T], target: T, k: usize) -> Combinations<T> {
+    let sorted = sort_entries(values);
+    Combinations {
+        prune: non_negative(&sorted),
+        sorted,
+        k,
+        target: target.widen(),
+        prefix: Vec::new(),
+        last: 0..0,
+        started: false,
+    }
+}
+
+/// Collects the combinations of `k` entries summing to `target`, at most
+/// `cap` of them if given.
+pub fn find_all<T: Number>(
+    values: &[T],
+    target: T,
+    k: usize,
+    cap: Option<usize>,
+) -> Vec<Combination<T>> {
+    combinations(values, target, k)
+        .take(cap.unwrap_or(usize::MAX))
+        .collect()
+}
+
+impl<T: Number> Combinations<T> {
+    fn remaining(&self) -> T::Sum {
+        self.prefix.iter().fold(self.target.clone(), |acc, &p| {
+            acc - self.sorted[p].0.widen()
+        })
+    }
+
+    // whether the entry at `pos` can follow the current prefix
+    fn fits(&self, pos: usize) -> bool {
+        pos < self.sorted.len() && (!self.prune || self.sorted[pos].0.widen() <= self.remaining())
+    }
+
+    // extends the prefix to k-1 entries with the smallest positions possible
+    fn fill(&mut self) -> bool {
+        while self.prefix.len() + 1 < self.k {
+            let pos = self.prefix.last().map_or(0, |&p| p + 1);
+            if !self.fits(pos) {
+                return false;
+            }
+            self.prefix.push(pos);
+        }
+        true
+    }
+
+    // moves the deepest entry of the prefix that can move to the next position
+    fn bump(&mut self) -> bool {
+        while let Some(pos) = self.prefix.pop() {
+            if self.fits(pos + 1) {
+                self.prefix.push(pos + 1);
+                return true;
+            }
+        }
+        false
+    }
+
+    // moves to the next prefix of k-1 entries and sets the range of the last one
+    fn next_prefix(&mut self) -> bool {
+        let mut found = !self.started && self.fill();
+        self.started = true;
+        while !found {
+            if !self.bump() {
+                return false;
+            }
+            found = self.fill();
+        }
+        let start = self.prefix.last().map_or(0, |&p| p + 1);
+        let rest = &self.sorted[start..];
+        let remaining = self.remaining();
+        let lo = rest.partition_point(|(v, _)| v.widen() < remaining);
+        let hi = rest.partition_point(|(v, _)| v.widen() <= remaining);
+        self.last = start + lo..start + hi;
+        true
+    }
+}
+
+impl<T: Number> Iterator for Combinations<T> {
+    type Item = Combination<T>;
+
+    fn next(&mut self) -> Option<Combination<T>> {
+        if self.k == 0 {
+            // the empty combination, once
+            let found = !self.started && self.target == T::Sum::default();
+            self.started = true;
+            return if found {
+                Some(Combination {
+                    indices: Vec::new(),
+                    values: Vec::new(),
+                })
+            } else {
+                None
+            };
+        }
+        loop {
+            if let Some(pos) = self.last.next() {
+                let mut entries: Vec<&(T, usize)> = self
+                    .prefix
+                    .iter()
+                    .chain(std::iter::once(&pos))
+                    .map(|&p| &self.sorted[p])
+                    .collect();
+                entries.sort_unstable_by_key(|&&(_, i)| i);
+                return Some(Combination {
+                    indices: entries.iter().map(|&&(_, i)| i).collect(),
+                    values: entries.iter().map(|(v, _)| v.clone()).collect(),
+                });
+            }
+            if !self.next_prefix() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: i64 = 2020;
+
+    #[test]
+    fn test_find() {
+        let numbers: Vec<i64> = vec![1721, 979, 366, 299, 675, 1456];
+        let two = find_sum(&numbers, TARGET, 2).unwrap();
+        assert_eq!(two.indices, vec![0, 3]);
+        assert_eq!(two.values, vec![1721, 299]);
+        assert_eq!(two.product(), BigInt::from(514579));
+        let three = find_sum(&numbers, TARGET, 3).unwrap();
+        assert_eq!(three.indices, vec![1, 2, 4]);
+        assert_eq!(three.product(), BigInt::from(241861950));
+        assert!(find_sum(&[1, 2], TARGET, 2).is_err());
+    }
+
+    #[test]
+    fn test_find_any_k() {
+        let numbers: Vec<u32> = vec![5, 1, 4, 2, 3];
+        assert_eq!(find_sum(&numbers, 4, 1).unwrap().indices, vec![2]);
+        assert_eq!(
+            find_sum(&numbers, 15, 5).unwrap().indices,
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(find_sum(&numbers, 10, 4).unwrap().values, vec![1, 4, 2, 3]);
+        assert!(find_sum(&numbers, 16, 5).is_err());
+        assert!(find_sum(&numbers, 1, 6).is_err());
+        assert_eq!(
+            find_sum(&numbers, 0, 0).unwrap().indices,
+            Vec::<usize>::new()
+        );
+        // the same value may be used once per occurrence
+        assert_eq!(
+            find_sum(&[1010, 7, 1010], TARGET, 2).unwrap().indices,
+            vec![0, 2]
+        );
+        assert!(find_sum(&[1010, 7], TARGET, 2).is_err());
+    }
+
+    #[test]
+    fn test_combinations() {
+        let indices = |combos: Vec<Combination<u32>>| -> Vec<Vec<usize>> {
+            combos.into_iter().map(|c| c.indices).collect()
+        };
+        let numbers: Vec<u32> = vec![1, 3, 2, 2, 4, 0];
+        assert_eq!(
+            indices(find_all(&numbers, 4, 2, None)),
+            vec![vec![4, 5], vec![0, 1], vec![2, 3]]
+        );
+        assert_eq!(
+            indices(find_all(&numbers, 5, 3, None)),
+            vec![vec![0, 4, 5], vec![1, 2, 5], vec![1, 3, 5], vec![0, 2, 3]]
+        );
+        assert_eq!(find_all(&numbers, 5, 3, Some(2)).len(), 2);
+        assert_eq!(find_all(&numbers, 12, 6, None).len(), 1);
+        assert!(find_all(&numbers, 13, 6, None).is_empty());
+        assert!(find_all(&numbers, 1, 7, None).is_empty());
+        assert_eq!(find_all(&numbers, 2, 1, None).len(), 2);
+        assert_eq!(
+            indices(find_all(&numbers, 0, 0, None)),
+            vec![Vec::<usize>::new()]
+        );
+        assert!(find_all(&numbers, 1, 0, None).is_empty());
+        // every value is equal: all 10 pairs out of 5 entries
+        assert_eq!(find_all(&[1010u32; 5], 2020, 2, None).len(), 10);
+    }
+
+    #[test]
+    fn test_signed() {
+        let numbers: Vec<i64> = vec![3000, -980, 10, -5, 2015];
+        assert_eq!(find_sum(&numbers, TARGET, 2).unwrap().indices, vec![0, 1]);
+        assert_eq!(
+            find_sum(&numbers, TARGET, 3).unwrap().values,
+            vec![10, -5, 2015]
+        );
+        assert_eq!(find_all(&numbers, TARGET, 3, None).len(), 1);
+        // sums and products past the i64 range
+        let numbers = vec![i64::MAX, 1, i64::MIN];
+        let two = find_sum(&numbers, -1, 2).unwrap();
+        assert_eq!(two.indices, vec![0, 2]);
+        assert_eq!(
+            two.product().to_string(),
+            "-85070591730234615856620279821087277056"
+        );
+        assert!(find_sum(&numbers, i64::MAX, 3).is_err());
+    }
+
+    #[test]
+    fn test_bigint() {
+        let big = |s: &str| s.parse::<BigInt>().unwrap();
+        let numbers = vec![
+            big("100000000000000000000"),
+            big("7"),
+            big("-99999999999999997980"),
+        ];
+        let two = find_sum(&numbers, big("2020"), 2).unwrap();
+        assert_eq!(two.indices, vec![0, 2]);
+        assert_eq!(
+            two.product(),
+            big("-9999999999999999798000000000000000000000")
+        );
+    }
+}
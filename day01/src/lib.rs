@@ -0,0 +1,147 @@
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+use utils::{Error, Input, ReadOptions, Result, Solution, Trim, Warning};
+
+pub mod ksum;
+
+pub use ksum::{combinations, find_all, find_sum, Combination, Combinations, Number};
+
+/// The sum the entries of the puzzle must add up to.
+pub const TARGET: i64 = 2020;
+
+/// The entries of the expense report: 64-bit when they all fit, arbitrary
+/// precision otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Entries {
+    Small(Vec<i64>),
+    Big(Vec<BigInt>),
+}
+
+impl Entries {
+    /// Product of `k` entries summing to `target`.
+    pub fn product_of_sum(&self, target: i64, k: usize) -> Result<BigInt> {
+        match self {
+            Entries::Small(values) => Ok(find_sum(values, target, k)?.product()),
+            Entries::Big(values) => Ok(find_sum(values, BigInt::from(target), k)?.product()),
+        }
+    }
+
+    /// Whether more than one combination of `k` entries sums to `target`.
+    pub fn is_ambiguous(&self, target: i64, k: usize) -> bool {
+        match self {
+            Entries::Small(values) => combinations(values, target, k).nth(1).is_some(),
+            Entries::Big(values) => combinations(values, BigInt::from(target), k)
+                .nth(1)
+                .is_some(),
+        }
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Entries;
+    type PartOne = BigInt;
+    type PartTwo = BigInt;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::Both).skip_empty()
+    }
+
+    // converts each line to integer, switching to arbitrary precision when
+    // an entry does not fit in 64 bits
+    fn parse(&self, input: &Input) -> Result<Entries> {
+        let mut big: Vec<BigInt> = Vec::new();
+        for line in input.lines() {
+            big.push(parse_number(&line.text).map_err(|e| e.at_line(line.number))?);
+        }
+        let small: Option<Vec<i64>> = big.iter().map(i64::try_from).map(|r| r.ok()).collect();
+        Ok(match small {
+            Some(values) => Entries::Small(values),
+            None => Entries::Big(big),
+        })
+    }
+
+    fn part_one(&self, entries: &Entries) -> Result<BigInt> {
+        entries.product_of_sum(TARGET, 2)
+    }
+
+    fn part_two(&self, entries: &Entries) -> Result<BigInt> {
+        entries.product_of_sum(TARGET, 3)
+    }
+
+    // the answers are ambiguous if several combinations sum to the target
+    fn warnings(&self, entries: &Entries) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        for &(part, k) in [("one", 2), ("two", 3)].iter() {
+            if entries.is_ambiguous(TARGET, k) {
+                warnings.push(Warning::new(format!(
+                    "part {}: several combinations of {} entries sum to {}, the answer is ambiguous",
+                    part, k, TARGET
+                )));
+            }
+        }
+        warnings
+    }
+}
+
+fn parse_number(text: &str) -> Result<BigInt> {
+    text.parse::<BigInt>()
+        .map_err(|err| Error::parse(format!("not a number: {:?} ({})", text, err)).at_column(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Entries> {
+        Day01.parse(&Input::from_text(text, Day01.read_options()))
+    }
+
+    #[test]
+    fn test_small() {
+        let entries = parse(include_str!("../testdata/small.txt")).unwrap();
+        assert_eq!(Day01.part_one(&entries).unwrap(), BigInt::from(514579));
+        assert_eq!(Day01.part_two(&entries).unwrap(), BigInt::from(241861950));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("-5\n+7\n").unwrap(), Entries::Small(vec![-5, 7]));
+        let entries = parse("1\n99999999999999999999\n").unwrap();
+        assert!(matches!(entries, Entries::Big(_)));
+        let err = parse("1721\n\n97x9\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: parse error: not a number: \"97x9\" (invalid digit found in string)"
+        );
+    }
+
+    #[test]
+    fn test_wide_entries() {
+        let entries = parse("3000000000\n-2999997980\n5\n").unwrap();
+        assert_eq!(
+            Day01.part_one(&entries).unwrap().to_string(),
+            "-8999993940000000000"
+        );
+        let entries = parse("10000000000000000000000\n-9999999999999999997980\n").unwrap();
+        assert_eq!(
+            Day01.part_one(&entries).unwrap().to_string(),
+            "-99999999999999999979800000000000000000000000"
+        );
+        assert!(Day01.part_two(&entries).is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_warning() {
+        let entries = parse("1721\n979\n366\n299\n675\n1456\n").unwrap();
+        assert!(Day01.warnings(&entries).is_empty());
+        let entries = parse("1721\n979\n366\n299\n675\n1456\n1010\n1010\n").unwrap();
+        let warnings = Day01.warnings(&entries);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0]
+            .message
+            .starts_with("part one: several combinations"));
+    }
+}
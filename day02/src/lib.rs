@@ -0,0 +1,368 @@
+pub mod report;
+pub mod rule;
+
+use unicode_segmentation::UnicodeSegmentation;
+use utils::input::tokens;
+use utils::{Error, Input, ReadOptions, Result, Solution, Trim};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<PasswordEntry>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Vec<PasswordEntry>> {
+        input
+            .lines()
+            .iter()
+            .map(|line| PasswordEntry::parse(&line.text, line.number))
+            .collect()
+    }
+
+    fn part_one(&self, entries: &Vec<PasswordEntry>) -> Result<usize> {
+        count_valid(entries, &CountInRange::default())
+    }
+
+    fn part_two(&self, entries: &Vec<PasswordEntry>) -> Result<usize> {
+        count_valid(entries, &ExactlyOnePosition::default())
+    }
+}
+
+/// One line of the password database: `<n>-<n> <letter>: <password>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasswordEntry {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// The two numbers of the policy, whose meaning depends on the policy.
+    pub first: usize,
+    pub second: usize,
+    /// The letter as written, checked against the unit of the policy when
+    /// evaluated.
+    pub letter: String,
+    pub password: String,
+    /// Byte column of the password in the line.
+    pub password_column: usize,
+}
+
+impl PasswordEntry {
+    /// Parses a line of the database; errors carry the line number and the
+    /// byte column of the offending token.
+    pub fn parse(text: &str, line: usize) -> Result<PasswordEntry> {
+        let (first, second, letter, (password_column, password)) =
+            split_line(text).map_err(|e| e.at_line(line))?;
+        Ok(PasswordEntry {
+            line,
+            first,
+            second,
+            letter: letter.to_string(),
+            password: password.to_string(),
+            password_column,
+        })
+    }
+}
+
+// split_line splits "<n>-<n> <letter>: <password>" into its parts, the
+// password with its byte column; errors carry the byte column of the
+// offending token.
+fn split_line(line: &str) -> Result<(usize, usize, &str, (usize, &str))> {
+    let v: Vec<(usize, &str)> = tokens(line).collect();
+    if v.len() != 3 {
+        return Err(Error::parse("bad input - expect <n>-<n> <letter>: <password>").at_column(1));
+    }
+    let (col, range) = v[0];
+    let indices: Vec<&str> = range.split('-').collect();
+    if indices.len() != 2 {
+        return Err(Error::parse("bad input - expect <n>-<n> pair of indices").at_column(col));
+    }
+    let first = indices[0]
+        .parse::<usize>()
+        .map_err(|e| Error::from(e).at_column(col))?;
+    let second = indices[1]
+        .parse::<usize>()
+        .map_err(|e| Error::from(e).at_column(col + indices[0].len() + 1))?;
+    let (col, letter) = v[1];
+    let letter = letter.strip_suffix(':').unwrap_or(letter);
+    if letter.is_empty() {
+        return Err(Error::parse("bad input - expect a letter before ':'").at_column(col));
+    }
+    Ok((first, second, letter, v[2]))
+}
+
+/// What the policies count as a letter of the password.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Unit {
+    /// A Unicode scalar value, i.e. a `char`.
+    #[default]
+    Char,
+    /// An extended grapheme cluster, what a reader sees as one character,
+    /// e.g. `e` followed by a combining accent.
+    Grapheme,
+}
+
+impl Unit {
+    /// Splits the text into units.
+    pub fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            Unit::Char => text
+                .char_indices()
+                .map(|(i, c)| &text[i..i + c.len_utf8()])
+                .collect(),
+            Unit::Grapheme => text.graphemes(true).collect(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Unit::Char => "character",
+            Unit::Grapheme => "grapheme cluster",
+        }
+    }
+
+    // the letter of the entry, which must be a single unit
+    fn letter(self, entry: &PasswordEntry) -> Result<&str> {
+        if self.split(&entry.letter).len() != 1 {
+            return Err(Error::validation(format!(
+                "letter {:?} is not a single {}",
+                entry.letter,
+                self.name()
+            ))
+            .at_line(entry.line));
+        }
+        Ok(&entry.letter)
+    }
+
+    // the unit at the 1-based position of the split password of the entry
+    fn at<'a>(self, entry: &PasswordEntry, units: &[&'a str], pos: usize) -> Result<&'a str> {
+        match pos.checked_sub(1).and_then(|i| units.get(i)) {
+            Some(&u) => Ok(u),
+            None => Err(Error::validation(format!(
+                "position {} is outside the password {:?} of {} {}s",
+                pos,
+                entry.password,
+                units.len(),
+                self.name()
+            ))
+            .at_line(entry.line)
+            .at_column(entry.password_column)),
+        }
+    }
+}
+
+/// Result of checking a password against a policy.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    /// The password breaks the policy, with the reason.
+    Fail(String),
+}
+
+/// A rule a password must satisfy.
+pub trait Policy {
+    /// Short name of the policy, as shown in reports.
+    fn name(&self) -> &str;
+
+    /// Checks the password of the entry, explaining failures; entries the
+    /// policy cannot be applied to give a validation error.
+    fn evaluate(&self, entry: &PasswordEntry) -> Result<Verdict>;
+
+    fn check(&self, entry: &PasswordEntry) -> Result<bool> {
+        Ok(self.evaluate(entry)? == Verdict::Pass)
+    }
+}
+
+/// Part one: the letter must appear between `first` and `second` times.
+#[derive(Default)]
+pub struct CountInRange {
+    pub unit: Unit,
+}
+
+impl Policy for CountInRange {
+    fn name(&self) -> &str {
+        "count-in-range"
+    }
+
+    fn evaluate(&self, entry: &PasswordEntry) -> Result<Verdict> {
+        let letter = self.unit.letter(entry)?;
+        let freq = self
+            .unit
+            .split(&entry.password)
+            .iter()
+            .filter(|&&u| u == letter)
+            .count();
+        if (entry.first..=entry.second).contains(&freq) {
+            Ok(Verdict::Pass)
+        } else {
+            Ok(Verdict::Fail(format!(
+                "'{}' appears {} times, allowed {} to {}",
+                entry.letter, freq, entry.first, entry.second
+            )))
+        }
+    }
+}
+
+/// Part two: the letter must be at exactly one of the 1-based positions
+/// `first` and `second`.
+#[derive(Default)]
+pub struct ExactlyOnePosition {
+    pub unit: Unit,
+}
+
+impl Policy for ExactlyOnePosition {
+    fn name(&self) -> &str {
+        "exactly-one-position"
+    }
+
+    fn evaluate(&self, entry: &PasswordEntry) -> Result<Verdict> {
+        let letter = self.unit.letter(entry)?;
+        let pw = self.unit.split(&entry.password);
+        let at = |pos: usize| -> Result<bool> { Ok(self.unit.at(entry, &pw, pos)? == letter) };
+        match (at(entry.first)?, at(entry.second)?) {
+            (true, false) | (false, true) => Ok(Verdict::Pass),
+            (true, true) => Ok(Verdict::Fail(format!(
+                "'{}' is at both positions {} and {}",
+                entry.letter, entry.first, entry.second
+            ))),
+            (false, false) => Ok(Verdict::Fail(format!(
+                "'{}' is at neither position {} nor {}",
+                entry.letter, entry.first, entry.second
+            ))),
+        }
+    }
+}
+
+/// Looks up one of the built-in policies by name; `1` and `2` stand for the
+/// policies of the two parts.
+pub fn policy_by_name(name: &str, unit: Unit) -> Option<Box<dyn Policy>> {
+    match name {
+        "1" | "count-in-range" => Some(Box::new(CountInRange { unit })),
+        "2" | "exactly-one-position" => Some(Box::new(ExactlyOnePosition { unit })),
+        _ => None,
+    }
+}
+
+/// Counts the entries whose password satisfies the policy.
+pub fn count_valid(entries: &[PasswordEntry], policy: &dyn Policy) -> Result<usize> {
+    let mut cnt = 0;
+    for entry in entries.iter() {
+        if policy.check(entry)? {
+            cnt += 1;
+        }
+    }
+    Ok(cnt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Vec<PasswordEntry>> {
+        Day02.parse(&Input::from_text(text, Day02.read_options()))
+    }
+
+    #[test]
+    fn test_small() {
+        let entries = parse(include_str!("../testdata/small.txt")).unwrap();
+        assert_eq!(Day02.part_one(&entries).unwrap(), 2);
+        assert_eq!(Day02.part_two(&entries).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            PasswordEntry::parse("2-9 c: ccccccccc", 3).unwrap(),
+            PasswordEntry {
+                line: 3,
+                first: 2,
+                second: 9,
+                letter: "c".to_string(),
+                password: "ccccccccc".to_string(),
+                password_column: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_line_errors() {
+        let err = split_line("1-x b: cdefg").unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(3));
+        assert!(split_line("1-3 b:").is_err());
+        let err = parse("1-3 a: abcde\n1-3 b cdefg x\n").unwrap_err();
+        assert_eq!(err.location().unwrap().line, Some(2));
+    }
+
+    #[test]
+    fn test_policies() {
+        let entry = PasswordEntry::parse("1-3 a: abcde", 1).unwrap();
+        assert!(CountInRange::default().check(&entry).unwrap());
+        assert!(ExactlyOnePosition::default().check(&entry).unwrap());
+        let entry = PasswordEntry::parse("2-9 c: ccccccccc", 1).unwrap();
+        assert!(CountInRange::default().check(&entry).unwrap());
+        assert!(!ExactlyOnePosition::default().check(&entry).unwrap());
+        assert_eq!(
+            ExactlyOnePosition::default().evaluate(&entry).unwrap(),
+            Verdict::Fail("'c' is at both positions 2 and 9".to_string())
+        );
+        let entry = PasswordEntry::parse("1-3 b: cdefg", 1).unwrap();
+        assert_eq!(
+            CountInRange::default().evaluate(&entry).unwrap(),
+            Verdict::Fail("'b' appears 0 times, allowed 1 to 3".to_string())
+        );
+        let entry = PasswordEntry::parse("1-7 a: abc", 4).unwrap();
+        let err = ExactlyOnePosition::default().check(&entry).unwrap_err();
+        assert_eq!(err.location().unwrap().line, Some(4));
+    }
+
+    #[test]
+    fn test_unicode() {
+        let count = CountInRange::default();
+        let position = ExactlyOnePosition::default();
+        let entry = PasswordEntry::parse("1-2 é: éxéé", 1).unwrap();
+        assert_eq!(entry.letter, "é");
+        assert!(!count.check(&entry).unwrap());
+        assert!(position.check(&entry).unwrap());
+        let entry = PasswordEntry::parse("2-4 ß: aßßd", 1).unwrap();
+        assert!(count.check(&entry).unwrap());
+        assert!(position.check(&entry).unwrap());
+        let entry = PasswordEntry::parse("1-2 😀: 😀😀", 1).unwrap();
+        assert!(count.check(&entry).unwrap());
+        assert!(!position.check(&entry).unwrap());
+        // positions count characters, not bytes
+        let entry = PasswordEntry::parse("1-5 é: ééééa", 9).unwrap();
+        assert!(position.check(&entry).unwrap());
+        let entry = PasswordEntry::parse("1-6 é: ééééa", 9).unwrap();
+        let err = position.check(&entry).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 9, column 9: validation error: position 6 is outside the password \"ééééa\" of 5 characters"
+        );
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // "e" followed by U+0301 COMBINING ACUTE ACCENT
+        let entry = PasswordEntry::parse("1-2 e\u{301}: e\u{301}xe", 2).unwrap();
+        let err = CountInRange::default().check(&entry).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: validation error: letter \"e\\u{301}\" is not a single character"
+        );
+        let count = CountInRange {
+            unit: Unit::Grapheme,
+        };
+        let position = ExactlyOnePosition {
+            unit: Unit::Grapheme,
+        };
+        assert!(count.check(&entry).unwrap());
+        // the plain "e" at position 3 is a different grapheme
+        let entry = PasswordEntry::parse("1-3 e\u{301}: e\u{301}xe", 2).unwrap();
+        assert!(position.check(&entry).unwrap());
+        assert_eq!(Unit::Grapheme.split("e\u{301}x").len(), 2);
+        assert_eq!(Unit::Char.split("e\u{301}x").len(), 3);
+    }
+}
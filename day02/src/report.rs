@@ -0,0 +1,260 @@
+//! Report mode: every entry of the database checked against one or more
+//! policies, with the reason of each failure, as CSV or JSON.
+//!
+//! `day02 report [--policy NAME | --rule EXPR | --rule-file PATH]... [--graphemes]
+//! [--format csv|json] [inputfile|-]`, see [`crate::rule`] for the rules.
+
+use crate::rule::Rule;
+use crate::{policy_by_name, Day02, PasswordEntry, Policy, Unit, Verdict};
+use serde_json::{json, Value};
+use utils::cli::{self, csv_field, TableFormat};
+use utils::{Input, Result, Solution, Source};
+
+/// Outcome of checking one entry against one policy.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    /// The policy could not be applied, e.g. a position past the end.
+    Error(String),
+}
+
+/// One line of the report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub entry: PasswordEntry,
+    pub policy: String,
+    pub status: Status,
+}
+
+impl Row {
+    fn result(&self) -> &str {
+        match self.status {
+            Status::Pass => "pass",
+            Status::Fail(_) => "fail",
+            Status::Error(_) => "error",
+        }
+    }
+
+    fn reason(&self) -> &str {
+        match &self.status {
+            Status::Pass => "",
+            Status::Fail(reason) | Status::Error(reason) => reason,
+        }
+    }
+}
+
+/// Checks every entry against every policy, entry by entry.
+pub fn report(entries: &[PasswordEntry], policies: &[Box<dyn Policy>]) -> Vec<Row> {
+    let mut rows = Vec::with_capacity(entries.len() * policies.len());
+    for entry in entries.iter() {
+        for policy in policies.iter() {
+            let status = match policy.evaluate(entry) {
+                Ok(Verdict::Pass) => Status::Pass,
+                Ok(Verdict::Fail(reason)) => Status::Fail(reason),
+                Err(err) => Status::Error(err.to_string()),
+            };
+            rows.push(Row {
+                entry: entry.clone(),
+                policy: policy.name().to_string(),
+                status,
+            });
+        }
+    }
+    rows
+}
+
+const CSV_HEADER: &str = "line,policy,first,second,letter,password,result,reason";
+
+/// The report as CSV, with a header line.
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for row in rows.iter() {
+        let e = &row.entry;
+        let fields = [
+            e.line.to_string(),
+            row.policy.clone(),
+            e.first.to_string(),
+            e.second.to_string(),
+            e.letter.clone(),
+            e.password.clone(),
+            row.result().to_string(),
+            row.reason().to_string(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// The report as a JSON array with one object per row.
+pub fn to_json(rows: &[Row]) -> Value {
+    let rows: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            let e = &row.entry;
+            json!({
+                "line": e.line,
+                "policy": row.policy,
+                "first": e.first,
+                "second": e.second,
+                "letter": e.letter,
+                "password": e.password,
+                "result": row.result(),
+                "reason": match row.status {
+                    Status::Pass => Value::Null,
+                    _ => Value::from(row.reason()),
+                },
+            })
+        })
+        .collect();
+    Value::Array(rows)
+}
+
+// a policy as given on the command line, built once the unit is known
+enum PolicyArg {
+    Name(String),
+    Rule(String),
+    RuleFile(Source),
+}
+
+struct Args {
+    policies: Vec<PolicyArg>,
+    unit: Unit,
+    format: TableFormat,
+    source: Source,
+}
+
+const USAGE: &str =
+    "usage: day02 report [--policy NAME | --rule EXPR | --rule-file PATH]... [--graphemes]
+                    [--format csv|json] [inputfile|-]
+policies: count-in-range (1), exactly-one-position (2); both by default
+rules: e.g. 'count(a) in 1..3 and (pos(1) = a xor pos(3) = a)'
+--graphemes: letters are grapheme clusters instead of Unicode scalar values";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut policies = Vec::new();
+    let mut unit = Unit::Char;
+    let mut format = TableFormat::Csv;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--policy" => {
+                let name = cli::option_value(arg, &mut it)?;
+                if policy_by_name(name, unit).is_none() {
+                    return Err(format!("unknown policy '{}'", name));
+                }
+                policies.push(PolicyArg::Name(name.to_string()));
+            }
+            "--rule" => {
+                let rule = cli::option_value(arg, &mut it)?;
+                policies.push(PolicyArg::Rule(rule.to_string()));
+            }
+            "--rule-file" => {
+                let path = cli::option_value(arg, &mut it)?;
+                policies.push(PolicyArg::RuleFile(Source::from_arg(path)));
+            }
+            "--graphemes" => unit = Unit::Grapheme,
+            "--format" => format = TableFormat::parse_arg(cli::option_value(arg, &mut it)?)?,
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    if policies.is_empty() {
+        policies = vec![
+            PolicyArg::Name("1".to_string()),
+            PolicyArg::Name("2".to_string()),
+        ];
+    }
+    Ok(Args {
+        policies,
+        unit,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn policy(arg: &PolicyArg, unit: Unit) -> Result<Box<dyn Policy>> {
+    Ok(match arg {
+        PolicyArg::Name(name) => policy_by_name(name, unit).expect("checked by parse_args"),
+        PolicyArg::Rule(text) => {
+            Box::new(Rule::parse(text, unit).map_err(|e| e.in_file("--rule"))?)
+        }
+        PolicyArg::RuleFile(source) => Box::new(Rule::read(source, unit)?),
+    })
+}
+
+fn run_report(args: &Args) -> Result<String> {
+    let policies = args
+        .policies
+        .iter()
+        .map(|arg| policy(arg, args.unit))
+        .collect::<Result<Vec<_>>>()?;
+    let input = Input::read(&args.source, Day02.read_options())?;
+    let entries = Day02.parse(&input).map_err(|e| e.in_file(input.name()))?;
+    let rows = report(&entries, &policies);
+    Ok(match args.format {
+        TableFormat::Csv => to_csv(&rows),
+        TableFormat::Json => format!("{}\n", to_json(&rows)),
+    })
+}
+
+/// Entry point of `day02 report`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(args, parse_args, run_report, USAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        let entries: Vec<PasswordEntry> = ["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: c,\"c"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| PasswordEntry::parse(text, i + 1).unwrap())
+            .collect();
+        let policies = vec![
+            policy_by_name("1", Unit::Char).unwrap(),
+            policy_by_name("2", Unit::Char).unwrap(),
+        ];
+        report(&entries, &policies)
+    }
+
+    #[test]
+    fn test_report() {
+        let rows = rows();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[1].policy, "exactly-one-position");
+        assert_eq!(rows[1].status, Status::Pass);
+        assert_eq!(
+            rows[3].status,
+            Status::Fail("'b' is at neither position 1 nor 3".to_string())
+        );
+        assert!(matches!(rows[5].status, Status::Error(_)));
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = to_csv(&rows());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "1,count-in-range,1,3,a,abcde,pass,");
+        assert_eq!(
+            lines[3],
+            "2,count-in-range,1,3,b,cdefg,fail,\"'b' appears 0 times, allowed 1 to 3\""
+        );
+        assert!(lines[5].starts_with("3,count-in-range,2,9,c,\"c,\"\"c\",pass,"));
+    }
+
+    #[test]
+    fn test_json() {
+        let json = to_json(&rows());
+        assert_eq!(json[0]["line"], 1);
+        assert_eq!(json[0]["reason"], Value::Null);
+        assert_eq!(json[2]["result"], "fail");
+        assert_eq!(json[5]["result"], "error");
+    }
+}
@@ -0,0 +1,709 @@
+//! A small language for password policies, so that new rules can be tried
+//! without writing Rust:
+//!
+//! ```text
+//! count(a) in 1..3 and (pos(1) = a xor pos(3) = a)
+//! ```
+//!
+//! Grammar, loosest binding first:
+//!
+//! ```text
+//! rule    = xor { "or" xor }
+//! xor     = and { "xor" and }
+//! and     = not { "and" not }
+//! not     = "not" not | "(" rule ")" | "true" | "false" | compare
+//! compare = number ( "=" | "!=" | "<" | "<=" | ">" | ">=" ) number
+//!         | number "in" number ".." number
+//!         | letter ( "=" | "!=" ) letter
+//! number  = digits | "first" | "second" | "len" | "count" "(" letter ")"
+//! letter  = "letter" | "pos" "(" number ")" | x | 'x' | "x"
+//! ```
+//!
+//! `first`, `second` and `letter` are the fields of the entry and `len` is
+//! the length of the password. Positions are 1-based and ranges include both
+//! ends. A bare letter is a single alphanumeric character other than a digit;
+//! digits and any other letters are quoted. `#` starts a comment running to
+//! the end of the line. `and` and `or` short-circuit, so
+//! `len >= 3 and pos(3) = a` never looks past the end of the password.
+//!
+//! The policies of the puzzle are `count(letter) in first..second` and
+//! `pos(first) = letter xor pos(second) = letter`.
+
+use crate::{PasswordEntry, Policy, Unit, Verdict};
+use std::fmt;
+use utils::{Error, Input, ReadOptions, Result, Source};
+
+/// A policy written in the rule language.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    name: String,
+    unit: Unit,
+    expr: Expr,
+}
+
+impl Rule {
+    /// Parses the text of a rule, which is also its name in reports, less
+    /// the comments; errors carry the line and byte column in the text.
+    pub fn parse(text: &str, unit: Unit) -> Result<Rule> {
+        let lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let name = text
+            .lines()
+            .flat_map(|line| strip_comment(line).split_whitespace())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Rule::from_lines(name, lines, unit)
+    }
+
+    /// Reads a rule from a file, named after the file.
+    pub fn read(source: &Source, unit: Unit) -> Result<Rule> {
+        let input = Input::read(source, ReadOptions::default())?;
+        let lines = input.lines().iter().map(|l| (l.number, l.text.as_str()));
+        Rule::from_lines(input.name().to_string(), lines, unit).map_err(|e| e.in_file(input.name()))
+    }
+
+    fn from_lines<'a>(
+        name: String,
+        lines: impl Iterator<Item = (usize, &'a str)>,
+        unit: Unit,
+    ) -> Result<Rule> {
+        let (tokens, end) = lex(lines)?;
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end,
+            unit,
+        };
+        if parser.tokens.is_empty() {
+            return Err(parser.error("empty rule"));
+        }
+        let expr = parser.rule()?;
+        if parser.next < parser.tokens.len() {
+            let found = parser.found();
+            return Err(parser.error(format!("expected 'and', 'or' or 'xor', found {}", found)));
+        }
+        Ok(Rule { name, unit, expr })
+    }
+}
+
+impl Policy for Rule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fails with the values the rule looked at, e.g.
+    /// `rule does not hold: count(letter) is 0`.
+    fn evaluate(&self, entry: &PasswordEntry) -> Result<Verdict> {
+        let mut ctx = Context {
+            entry,
+            unit: self.unit,
+            units: self.unit.split(&entry.password),
+            seen: Vec::new(),
+        };
+        if ctx.eval(&self.expr)? {
+            Ok(Verdict::Pass)
+        } else if ctx.seen.is_empty() {
+            Ok(Verdict::Fail("rule does not hold".to_string()))
+        } else {
+            Ok(Verdict::Fail(format!(
+                "rule does not hold: {}",
+                ctx.seen.join(", ")
+            )))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Const(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Numbers(Number, Op, Number),
+    /// Only `=` and `!=` are allowed between letters.
+    Letters(Letter, Op, Letter),
+    In(Number, Number, Number),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn from_symbol(symbol: &str) -> Option<Op> {
+        match symbol {
+            "=" => Some(Op::Eq),
+            "!=" => Some(Op::Ne),
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            ">" => Some(Op::Gt),
+            ">=" => Some(Op::Ge),
+            _ => None,
+        }
+    }
+
+    fn apply<T: PartialOrd + ?Sized>(self, a: &T, b: &T) -> bool {
+        match self {
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Number {
+    Literal(usize),
+    First,
+    Second,
+    Len,
+    Count(Letter),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Letter {
+    Literal(String),
+    /// The letter of the entry.
+    Entry,
+    Pos(Box<Number>),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Literal(n) => write!(f, "{}", n),
+            Number::First => write!(f, "first"),
+            Number::Second => write!(f, "second"),
+            Number::Len => write!(f, "len"),
+            Number::Count(letter) => write!(f, "count({})", letter),
+        }
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Letter::Literal(s) => write!(f, "'{}'", s),
+            Letter::Entry => write!(f, "letter"),
+            Letter::Pos(n) => write!(f, "pos({})", n),
+        }
+    }
+}
+
+// the state of evaluating a rule against one entry
+struct Context<'a> {
+    entry: &'a PasswordEntry,
+    unit: Unit,
+    units: Vec<&'a str>,
+    /// What the rule looked at, e.g. "pos(1) is 'a'", for the failure reason.
+    seen: Vec<String>,
+}
+
+impl<'a> Context<'a> {
+    fn eval(&mut self, expr: &'a Expr) -> Result<bool> {
+        Ok(match expr {
+            Expr::Const(b) => *b,
+            Expr::Not(e) => !self.eval(e)?,
+            Expr::And(a, b) => self.eval(a)? && self.eval(b)?,
+            Expr::Or(a, b) => self.eval(a)? || self.eval(b)?,
+            Expr::Xor(a, b) => self.eval(a)? != self.eval(b)?,
+            Expr::Numbers(a, op, b) => op.apply(&self.number(a)?, &self.number(b)?),
+            Expr::Letters(a, op, b) => op.apply(self.letter(a)?, self.letter(b)?),
+            Expr::In(n, lo, hi) => {
+                let n = self.number(n)?;
+                (self.number(lo)?..=self.number(hi)?).contains(&n)
+            }
+        })
+    }
+
+    fn number(&mut self, number: &'a Number) -> Result<usize> {
+        let value = match number {
+            Number::Literal(n) => return Ok(*n),
+            Number::First => return Ok(self.entry.first),
+            Number::Second => return Ok(self.entry.second),
+            Number::Len => self.units.len(),
+            Number::Count(letter) => {
+                let letter = self.letter(letter)?;
+                self.units.iter().filter(|&&u| u == letter).count()
+            }
+        };
+        self.note(number, value);
+        Ok(value)
+    }
+
+    fn letter(&mut self, letter: &'a Letter) -> Result<&'a str> {
+        match letter {
+            Letter::Literal(s) => Ok(s),
+            Letter::Entry => self.unit.letter(self.entry),
+            Letter::Pos(pos) => {
+                let pos = self.number(pos)?;
+                let value = self.unit.at(self.entry, &self.units, pos)?;
+                self.note(letter, format!("'{}'", value));
+                Ok(value)
+            }
+        }
+    }
+
+    fn note<T: fmt::Display, V: fmt::Display>(&mut self, term: &T, value: V) {
+        let seen = format!("{} is {}", term, value);
+        if !self.seen.contains(&seen) {
+            self.seen.push(seen);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Number(usize),
+    Quoted(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Word(w) => write!(f, "'{}'", w),
+            Token::Number(n) => write!(f, "'{}'", n),
+            Token::Quoted(s) => write!(f, "{:?}", s),
+            Token::Symbol(s) => write!(f, "'{}'", s),
+        }
+    }
+}
+
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+// the line before its comment; a quoted '#' is a letter, not a comment
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match quote {
+            None if c == '#' => return &line[..i],
+            None if c == '\'' || c == '"' => quote = Some(c),
+            Some(q) if c == q => quote = None,
+            _ => (),
+        }
+    }
+    line
+}
+
+// longer symbols first, so that "<=" is not read as "<"
+const SYMBOLS: [&str; 10] = ["..", "!=", "<=", ">=", "==", "=", "<", ">", "(", ")"];
+
+// lex splits the lines into tokens with their line and byte column; it also
+// returns the location just past the last token, for errors at the end.
+fn lex<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<(Vec<Spanned>, (usize, usize))> {
+    let mut tokens = Vec::new();
+    let mut end = (1, 1);
+    for (line, text) in lines {
+        let mut i = 0;
+        while let Some(c) = text[i..].chars().next() {
+            let rest = &text[i..];
+            let column = i + 1;
+            let error = |msg: String| Error::parse(msg).at_line(line).at_column(column);
+            let (token, len) = if c.is_whitespace() {
+                i += c.len_utf8();
+                continue;
+            } else if c == '#' {
+                break;
+            } else if c.is_ascii_digit() {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let n = rest[..len]
+                    .parse()
+                    .map_err(|e| Error::from(e).at_line(line).at_column(column))?;
+                (Token::Number(n), len)
+            } else if c.is_alphanumeric() || c == '_' {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (Token::Word(rest[..len].to_string()), len)
+            } else if c == '\'' || c == '"' {
+                let close = rest[1..]
+                    .find(c)
+                    .ok_or_else(|| error(format!("unterminated quote {}", c)))?;
+                (Token::Quoted(rest[1..close + 1].to_string()), close + 2)
+            } else if let Some(&symbol) = SYMBOLS.iter().find(|s| rest.starts_with(*s)) {
+                let token = Token::Symbol(if symbol == "==" { "=" } else { symbol });
+                (token, symbol.len())
+            } else {
+                return Err(error(format!("unexpected character {:?}", c)));
+            };
+            tokens.push(Spanned {
+                token,
+                line,
+                column,
+            });
+            i += len;
+            end = (line, i + 1);
+        }
+    }
+    Ok((tokens, end))
+}
+
+const KEYWORDS: [&str; 7] = ["and", "or", "xor", "not", "in", "true", "false"];
+
+// a number or a letter, before the comparison settles which one is needed
+enum Term {
+    Number(Number),
+    Letter(Letter),
+}
+
+// recursive descent over the tokens, one method per rule of the grammar
+struct Parser {
+    tokens: Vec<Spanned>,
+    next: usize,
+    end: (usize, usize),
+    unit: Unit,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|t| &t.token)
+    }
+
+    // an error at the next token, or at the end of the rule
+    fn error<S: Into<String>>(&self, message: S) -> Error {
+        let (line, column) = match self.tokens.get(self.next) {
+            Some(t) => (t.line, t.column),
+            None => self.end,
+        };
+        Error::parse(message).at_line(line).at_column(column)
+    }
+
+    fn found(&self) -> String {
+        match self.peek() {
+            Some(token) => token.to_string(),
+            None => "the end of the rule".to_string(),
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        self.eat(&Token::Word(word.to_string()))
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<()> {
+        if self.eat(&Token::Symbol(symbol)) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}', found {}", symbol, self.found())))
+        }
+    }
+
+    fn rule(&mut self) -> Result<Expr> {
+        let mut expr = self.xor()?;
+        while self.eat_word("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.xor()?));
+        }
+        Ok(expr)
+    }
+
+    fn xor(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.eat_word("xor") {
+            expr = Expr::Xor(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.not()?;
+        while self.eat_word("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.eat_word("not") {
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else if self.eat(&Token::Symbol("(")) {
+            let expr = self.rule()?;
+            self.expect(")")?;
+            Ok(expr)
+        } else if self.eat_word("true") {
+            Ok(Expr::Const(true))
+        } else if self.eat_word("false") {
+            Ok(Expr::Const(false))
+        } else {
+            self.compare()
+        }
+    }
+
+    fn compare(&mut self) -> Result<Expr> {
+        let start = self.next;
+        let left = self.term()?;
+        if self.eat_word("in") {
+            let n = match left {
+                Term::Number(n) => n,
+                Term::Letter(_) => {
+                    self.next = start;
+                    return Err(self.error("expected a number before 'in'"));
+                }
+            };
+            let lo = self.number()?;
+            self.expect("..")?;
+            let hi = self.number()?;
+            return Ok(Expr::In(n, lo, hi));
+        }
+        let op = match self.peek() {
+            Some(Token::Symbol(s)) => Op::from_symbol(s),
+            _ => None,
+        };
+        let op = op.ok_or_else(|| {
+            self.error(format!(
+                "expected a comparison or 'in', found {}",
+                self.found()
+            ))
+        })?;
+        let at_op = self.next;
+        self.next += 1;
+        let right = self.term()?;
+        match (left, right) {
+            (Term::Number(a), Term::Number(b)) => Ok(Expr::Numbers(a, op, b)),
+            (Term::Letter(a), Term::Letter(b)) if op == Op::Eq || op == Op::Ne => {
+                Ok(Expr::Letters(a, op, b))
+            }
+            (Term::Letter(_), Term::Letter(_)) => {
+                self.next = at_op;
+                Err(self.error("letters can only be compared with '=' or '!='"))
+            }
+            _ => {
+                self.next = at_op;
+                Err(self.error("cannot compare a number with a letter"))
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Number> {
+        let start = self.next;
+        match self.term()? {
+            Term::Number(n) => Ok(n),
+            Term::Letter(letter) => {
+                self.next = start;
+                Err(self.error(format!("expected a number, found the letter {}", letter)))
+            }
+        }
+    }
+
+    fn letter(&mut self) -> Result<Letter> {
+        let start = self.next;
+        match self.term()? {
+            Term::Letter(letter) => Ok(letter),
+            Term::Number(n) => {
+                self.next = start;
+                Err(self.error(format!("expected a letter, found the number {}", n)))
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Term> {
+        let token = match self.peek() {
+            Some(token) => token.clone(),
+            None => {
+                return Err(self.error("expected a number or a letter, found the end of the rule"))
+            }
+        };
+        let term = match &token {
+            Token::Number(n) => Term::Number(Number::Literal(*n)),
+            Token::Quoted(s) if self.unit.split(s).len() == 1 => {
+                Term::Letter(Letter::Literal(s.clone()))
+            }
+            Token::Quoted(s) => {
+                return Err(self.error(format!("{:?} is not a single {}", s, self.unit.name())))
+            }
+            Token::Word(w) => match w.as_str() {
+                "first" => Term::Number(Number::First),
+                "second" => Term::Number(Number::Second),
+                "len" => Term::Number(Number::Len),
+                "letter" => Term::Letter(Letter::Entry),
+                "count" => {
+                    self.next += 1;
+                    self.expect("(")?;
+                    let letter = self.letter()?;
+                    self.expect(")")?;
+                    return Ok(Term::Number(Number::Count(letter)));
+                }
+                "pos" => {
+                    self.next += 1;
+                    self.expect("(")?;
+                    let pos = self.number()?;
+                    self.expect(")")?;
+                    return Ok(Term::Letter(Letter::Pos(Box::new(pos))));
+                }
+                w if KEYWORDS.contains(&w) => {
+                    return Err(
+                        self.error(format!("expected a number or a letter, found {}", token))
+                    )
+                }
+                w if self.unit.split(w).len() == 1 => Term::Letter(Letter::Literal(w.to_string())),
+                w => return Err(self.error(format!("unknown name '{}'", w))),
+            },
+            Token::Symbol(_) => {
+                return Err(self.error(format!("expected a number or a letter, found {}", token)))
+            }
+        };
+        self.next += 1;
+        Ok(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_valid, CountInRange, Day02, ExactlyOnePosition};
+    use utils::Solution;
+
+    fn entry(text: &str) -> PasswordEntry {
+        PasswordEntry::parse(text, 1).unwrap()
+    }
+
+    fn check(rule: &str, text: &str) -> bool {
+        Rule::parse(rule, Unit::Char)
+            .unwrap()
+            .check(&entry(text))
+            .unwrap()
+    }
+
+    fn parse_error(rule: &str) -> String {
+        Rule::parse(rule, Unit::Char).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_puzzle_rules() {
+        let input = Input::from_text(include_str!("../testdata/input.txt"), Day02.read_options());
+        let entries = Day02.parse(&input).unwrap();
+        let one = Rule::parse("count(letter) in first..second", Unit::Char).unwrap();
+        let two = Rule::parse("pos(first) = letter xor pos(second) = letter", Unit::Char).unwrap();
+        assert_eq!(
+            count_valid(&entries, &one).unwrap(),
+            count_valid(&entries, &CountInRange::default()).unwrap()
+        );
+        assert_eq!(
+            count_valid(&entries, &two).unwrap(),
+            count_valid(&entries, &ExactlyOnePosition::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let rule = "count(a) in 1..3 and (pos(1)=a xor pos(3)=a)";
+        assert!(check(rule, "1-3 x: abcde"));
+        assert!(!check(rule, "1-3 x: abade"));
+        assert!(!check(rule, "1-3 x: bbbde"));
+        assert!(check("not len < 8 or count('!') >= 1", "1-1 x: pass!"));
+        assert!(!check("not len < 8 or count(\"!\") >= 1", "1-1 x: pass"));
+        assert!(check("pos(len) != letter and true", "1-1 x: xxy"));
+        assert!(check("count(é) = 2 and pos(2) == 'é'", "1-1 x: éé"));
+        // and binds tighter than xor, which binds tighter than or
+        assert!(check("true or false xor true and false", "1-1 x: a"));
+        assert!(!check("(true or false) xor true", "1-1 x: a"));
+    }
+
+    #[test]
+    fn test_reason() {
+        let rule = Rule::parse("count(letter) in first..second", Unit::Char).unwrap();
+        assert_eq!(
+            rule.evaluate(&entry("1-3 b: cdefg")).unwrap(),
+            Verdict::Fail("rule does not hold: count(letter) is 0".to_string())
+        );
+        let rule = Rule::parse("pos(1) = a xor pos(3) = a", Unit::Char).unwrap();
+        assert_eq!(
+            rule.evaluate(&entry("1-3 a: cdefg")).unwrap(),
+            Verdict::Fail("rule does not hold: pos(1) is 'c', pos(3) is 'e'".to_string())
+        );
+        assert_eq!(rule.name(), "pos(1) = a xor pos(3) = a");
+    }
+
+    #[test]
+    fn test_positions() {
+        // and short-circuits before looking past the end
+        assert!(!check("len >= 9 and pos(9) = a", "1-1 a: abc"));
+        let rule = Rule::parse("pos(9) = a", Unit::Char).unwrap();
+        let err = rule.check(&entry("1-1 a: abc")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 8: validation error: position 9 is outside the password \"abc\" of 3 characters"
+        );
+        let rule = Rule::parse("pos(1) = 'e\u{301}'", Unit::Grapheme).unwrap();
+        assert!(rule.check(&entry("1-1 x: e\u{301}x")).unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_error(""), "line 1, column 1: parse error: empty rule");
+        assert_eq!(
+            parse_error("count(a) in 1..3 and"),
+            "line 1, column 21: parse error: expected a number or a letter, found the end of the rule"
+        );
+        assert_eq!(
+            parse_error("count(a) = a"),
+            "line 1, column 10: parse error: cannot compare a number with a letter"
+        );
+        assert_eq!(
+            parse_error("pos(1) < a"),
+            "line 1, column 8: parse error: letters can only be compared with '=' or '!='"
+        );
+        assert_eq!(
+            parse_error("count(ab) = 1"),
+            "line 1, column 7: parse error: unknown name 'ab'"
+        );
+        assert_eq!(
+            parse_error("pos(a) = a"),
+            "line 1, column 5: parse error: expected a number, found the letter 'a'"
+        );
+        assert_eq!(
+            parse_error("(len > 1"),
+            "line 1, column 9: parse error: expected ')', found the end of the rule"
+        );
+        assert_eq!(
+            parse_error("len > 1 len"),
+            "line 1, column 9: parse error: expected 'and', 'or' or 'xor', found 'len'"
+        );
+        assert_eq!(
+            parse_error("len > 1 and\n  pos(1) = 'ab'"),
+            "line 2, column 12: parse error: \"ab\" is not a single character"
+        );
+        assert_eq!(
+            parse_error("len ~ 1"),
+            "line 1, column 5: parse error: unexpected character '~'"
+        );
+    }
+
+    #[test]
+    fn test_comments() {
+        let text = "# at most two digits\ncount('1') <= 2  # ones\n  and count('2') <= 2\n";
+        let rule = Rule::parse(text, Unit::Char).unwrap();
+        assert!(rule.check(&entry("1-1 x: 1122")).unwrap());
+        assert!(!rule.check(&entry("1-1 x: 1112")).unwrap());
+        assert_eq!(rule.name(), "count('1') <= 2 and count('2') <= 2");
+        let rule = Rule::parse("pos(1) = '#'  # a hash first", Unit::Char).unwrap();
+        assert_eq!(rule.name(), "pos(1) = '#'");
+        assert!(rule.check(&entry("1-1 x: #a")).unwrap());
+    }
+}
@@ -0,0 +1,651 @@
+pub mod render;
+pub mod slopes;
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use utils::{Error, Input, Line, ReadOptions, Result, Solution, Trim};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = TreeMap;
+    type PartOne = u32;
+    type PartTwo = u64;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<TreeMap> {
+        TreeMap::parse(input.lines())
+    }
+
+    fn part_one(&self, map: &TreeMap) -> Result<u32> {
+        map.count_trees(Slope::new(3, 1))
+    }
+
+    fn part_two(&self, map: &TreeMap) -> Result<u64> {
+        survey(map, &SLOPES)?.product.ok_or_else(|| {
+            Error::validation("the product of the tree counts does not fit in 64 bits")
+        })
+    }
+}
+
+/// The slopes of part two.
+pub const SLOPES: [Slope; 5] = [
+    Slope::new(1, 1),
+    Slope::new(3, 1),
+    Slope::new(5, 1),
+    Slope::new(7, 1),
+    Slope::new(1, 2),
+];
+
+/// A move of the toboggan: `right` columns (leftward when negative) for
+/// every `down` rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Slope {
+    pub right: isize,
+    pub down: usize,
+}
+
+impl Slope {
+    pub const fn new(right: isize, down: usize) -> Slope {
+        Slope { right, down }
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.right, self.down)
+    }
+}
+
+/// Parses `<right>,<down>`, e.g. `3,1` or `-1,2`.
+impl FromStr for Slope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Slope> {
+        let (right, down) = s
+            .split_once(',')
+            .ok_or_else(|| Error::parse(format!("bad slope '{}' - expect <right>,<down>", s)))?;
+        let right = right.trim().parse::<isize>()?;
+        let down = down.trim().parse::<usize>()?;
+        if down == 0 {
+            return Err(Error::validation(format!(
+                "bad slope '{}' - down must be at least 1",
+                s
+            )));
+        }
+        Ok(Slope { right, down })
+    }
+}
+
+/// What a kind of terrain is called and what hitting it costs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Terrain {
+    pub name: String,
+    pub cost: u32,
+}
+
+/// The characters a map may contain and the terrain each stands for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Legend {
+    entries: Vec<(char, Terrain)>,
+}
+
+impl Legend {
+    /// An empty legend, see [`Legend::with`].
+    pub fn new() -> Legend {
+        Legend {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds or replaces the terrain of a character.
+    pub fn with(mut self, c: char, name: &str, cost: u32) -> Legend {
+        let terrain = Terrain {
+            name: name.to_string(),
+            cost,
+        };
+        match self.entries.iter_mut().find(|(e, _)| *e == c) {
+            Some(entry) => entry.1 = terrain,
+            None => self.entries.push((c, terrain)),
+        }
+        self
+    }
+
+    fn index(&self, c: char) -> Option<usize> {
+        self.entries.iter().position(|&(e, _)| e == c)
+    }
+}
+
+/// `.` is open ground and `#` a tree costing 1, as in the puzzle.
+impl Default for Legend {
+    fn default() -> Legend {
+        Legend::new().with('.', "open", 0).with('#', "tree", 1)
+    }
+}
+
+/// Parses a comma-separated list of `<char>=<name>[:<cost>]`, e.g.
+/// `.=open,#=tree:1,R=rock:5`; the cost is 0 when left out.
+impl FromStr for Legend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Legend> {
+        let mut legend = Legend::new();
+        for entry in s.split(',') {
+            let bad = || {
+                Error::parse(format!(
+                    "bad legend entry '{}' - expect <char>=<name>[:<cost>]",
+                    entry
+                ))
+            };
+            let (c, terrain) = entry.split_once('=').ok_or_else(bad)?;
+            let mut chars = c.chars();
+            let c = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(bad()),
+            };
+            let (name, cost) = match terrain.split_once(':') {
+                Some((name, cost)) => (name, cost.parse::<u32>()?),
+                None => (terrain, 0),
+            };
+            if name.is_empty() {
+                return Err(bad());
+            }
+            legend = legend.with(c, name, cost);
+        }
+        Ok(legend)
+    }
+}
+
+/// What happens when the path leaves the map at the left or right edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Boundary {
+    /// The pattern repeats, as in the puzzle.
+    #[default]
+    Wrap,
+    /// The path keeps going down along the edge.
+    Clamp,
+    /// The path ends.
+    Stop,
+}
+
+/// Parses `wrap`, `clamp` or `stop`.
+impl FromStr for Boundary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Boundary> {
+        match s {
+            "wrap" => Ok(Boundary::Wrap),
+            "clamp" => Ok(Boundary::Clamp),
+            "stop" => Ok(Boundary::Stop),
+            _ => Err(Error::parse(format!(
+                "bad boundary '{}' - expect wrap, clamp or stop",
+                s
+            ))),
+        }
+    }
+}
+
+/// The map of the area, each square one kind of terrain of the legend;
+/// "trees" are the squares whose terrain costs anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeMap {
+    width: usize,
+    /// Row-major indices into the legend.
+    squares: Vec<u8>,
+    legend: Legend,
+    boundary: Boundary,
+}
+
+impl TreeMap {
+    /// Builds the map from lines of `.` (open) and `#` (tree), all of the
+    /// same length, wrapping around at the edges.
+    pub fn parse(lines: &[Line]) -> Result<TreeMap> {
+        TreeMap::parse_with(lines, Legend::default())
+    }
+
+    /// Builds the map from lines of the characters of the legend, all of
+    /// the same length.
+    pub fn parse_with(lines: &[Line], legend: Legend) -> Result<TreeMap> {
+        if legend.entries.len() > usize::from(u8::MAX) + 1 {
+            return Err(Error::validation("the legend has more than 256 entries"));
+        }
+        validate_input(lines)?;
+        let width = lines[0].text.chars().count();
+        let mut squares = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            for (i, c) in line.text.char_indices() {
+                match legend.index(c) {
+                    Some(index) => squares.push(index as u8),
+                    None => {
+                        return Err(Error::parse(format!(
+                            "bad input - illegal character {:?} not in the legend",
+                            c
+                        ))
+                        .at_line(line.number)
+                        .at_column(i + 1))
+                    }
+                }
+            }
+        }
+        Ok(TreeMap {
+            width,
+            squares,
+            legend,
+            boundary: Boundary::Wrap,
+        })
+    }
+
+    /// The map with another behaviour at the left and right edges.
+    pub fn with_boundary(mut self, boundary: Boundary) -> TreeMap {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Width of the map, or of the repeated pattern when wrapping.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.squares.len() / self.width
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// The character and terrain at the 0-based row and column; columns
+    /// past either edge wrap around.
+    pub fn terrain(&self, row: usize, col: isize) -> (char, &Terrain) {
+        let col = col.rem_euclid(self.width as isize) as usize;
+        let (c, terrain) = &self.legend.entries[self.squares[row * self.width + col] as usize];
+        (*c, terrain)
+    }
+
+    /// Whether the terrain at the 0-based row and column costs anything.
+    pub fn is_tree(&self, row: usize, col: isize) -> bool {
+        self.terrain(row, col).1.cost > 0
+    }
+
+    /// The squares visited from the top left corner down to the bottom row,
+    /// as 0-based (row, column), the columns unwrapped when wrapping; the
+    /// start is not included.
+    ///
+    /// When the unwrapped columns do not fit in an `isize`, `right` is taken
+    /// modulo the width, which visits the same squares of the pattern; without
+    /// wrapping the columns saturate, the path leaving the map anyway.
+    pub fn path(&self, slope: Slope) -> Result<impl Iterator<Item = (usize, isize)>> {
+        if slope.down == 0 {
+            return Err(Error::validation("bad slope - down must be at least 1"));
+        }
+        let (boundary, last) = (self.boundary, self.width as isize - 1);
+        let steps = (self.height() - 1) / slope.down;
+        let right = match boundary {
+            Boundary::Wrap if (steps as isize).checked_mul(slope.right).is_none() => {
+                slope.right.rem_euclid(self.width as isize)
+            }
+            _ => slope.right,
+        };
+        Ok((slope.down..self.height())
+            .step_by(slope.down)
+            .enumerate()
+            .map(move |(i, row)| {
+                let col = (i as isize + 1).saturating_mul(right);
+                match boundary {
+                    Boundary::Clamp => (row, col.clamp(0, last)),
+                    _ => (row, col),
+                }
+            })
+            .take_while(move |&(_, col)| boundary != Boundary::Stop || (0..=last).contains(&col)))
+    }
+
+    /// Number of trees hit going down the slope.
+    pub fn count_trees(&self, slope: Slope) -> Result<u32> {
+        Ok(self
+            .path(slope)?
+            .filter(|&(row, col)| self.is_tree(row, col))
+            .count() as u32)
+    }
+
+    /// Total cost of the terrain hit going down the slope.
+    pub fn cost(&self, slope: Slope) -> Result<u64> {
+        Ok(self
+            .path(slope)?
+            .map(|(row, col)| u64::from(self.terrain(row, col).1.cost))
+            .sum())
+    }
+
+    /// Trees hit and their cost going down the slope.
+    pub fn count(&self, slope: Slope) -> Result<SlopeCount> {
+        let mut count = SlopeCount {
+            slope,
+            trees: 0,
+            cost: 0,
+        };
+        for (row, col) in self.path(slope)? {
+            let cost = self.terrain(row, col).1.cost;
+            if cost > 0 {
+                count.trees += 1;
+                count.cost += u64::from(cost);
+            }
+        }
+        Ok(count)
+    }
+}
+
+// ensure all the lines are the same length
+fn validate_input(lines: &[Line]) -> Result<()> {
+    if lines.is_empty() {
+        return Err(Error::validation("empty input file"));
+    }
+    let linelen = lines[0].text.chars().count();
+    if linelen == 0 {
+        return Err(Error::validation("bad input - the map has no columns")
+            .at_line(lines[0].number)
+            .at_column(1));
+    }
+    for line in lines.iter() {
+        let len = line.text.chars().count();
+        if len != linelen {
+            // byte column of the first character past the shorter length
+            let col = line
+                .text
+                .char_indices()
+                .nth(linelen.min(len))
+                .map_or(line.text.len(), |(i, _)| i);
+            return Err(Error::validation(format!(
+                "bad input - line length {} differs from the first line length {}",
+                len, linelen
+            ))
+            .at_line(line.number)
+            .at_column(col + 1));
+        }
+    }
+    Ok(())
+}
+
+/// Trees hit going down a slope and their total cost.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SlopeCount {
+    pub slope: Slope,
+    pub trees: u32,
+    pub cost: u64,
+}
+
+/// Tree counts of a list of slopes and the product of the counts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Survey {
+    pub counts: Vec<SlopeCount>,
+    /// `None` when the product does not fit in 64 bits.
+    pub product: Option<u64>,
+}
+
+/// Counts the trees on each slope, in order.
+pub fn survey(map: &TreeMap, slopes: &[Slope]) -> Result<Survey> {
+    let mut counts = Vec::with_capacity(slopes.len());
+    let mut product = Some(1u64);
+    for &slope in slopes.iter() {
+        let count = map.count(slope)?;
+        product = product.and_then(|p| p.checked_mul(u64::from(count.trees)));
+        counts.push(count);
+    }
+    Ok(Survey { counts, product })
+}
+
+/// Whether a slope search looks for the lowest or the highest cost.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Goal {
+    Fewest,
+    Most,
+}
+
+/// Finds the slope with `right` and `down` within the bounds whose path
+/// costs the least or the most, with the default legend the fewest or most
+/// trees; ties go to the smallest `down`, then the smallest `right`.
+///
+/// Slopes whose `right` differ by a multiple of the width visit the same
+/// squares when wrapping, so only the first `width` values of `right` are
+/// counted for each `down`; without wrapping all moves of a width or more to
+/// one side leave the map at once. A single slope is counted once `down`
+/// goes past the bottom row.
+pub fn best_slope(
+    map: &TreeMap,
+    right: RangeInclusive<isize>,
+    down: RangeInclusive<usize>,
+    goal: Goal,
+) -> Result<SlopeCount> {
+    if right.is_empty() || down.is_empty() {
+        return Err(Error::validation("empty range of slopes"));
+    }
+    if *down.start() == 0 {
+        return Err(Error::validation("bad slope - down must be at least 1"));
+    }
+    let (start, end, width) = (*right.start(), *right.end(), map.width() as isize);
+    let mut best: Option<SlopeCount> = None;
+    for d in down {
+        let rest = if d >= map.height() {
+            start.saturating_add(1)..=start
+        } else if map.boundary() == Boundary::Wrap {
+            start.saturating_add(1)..=end.min(start.saturating_add(width - 1))
+        } else {
+            // below -width the path leaves (or clamps) at once like for start,
+            // above width like for width
+            start.saturating_add(1).max(-width)..=end.min(width)
+        };
+        for r in std::iter::once(start).chain(rest) {
+            let count = map.count(Slope::new(r, d))?;
+            let better = match (best, goal) {
+                (None, _) => true,
+                (Some(b), Goal::Fewest) => count.cost < b.cost,
+                (Some(b), Goal::Most) => count.cost > b.cost,
+            };
+            if better {
+                best = Some(count);
+            }
+        }
+    }
+    Ok(best.expect("the ranges are not empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small() {
+        let input = Input::from_text(
+            "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#\n.#...##..#.\n\
+             ..#.##.....\n.#.#.#....#\n.#........#\n#.##...#...\n#...##....#\n.#..#...#.#\n",
+            Day03.read_options(),
+        );
+        let map = Day03.parse(&input).unwrap();
+        assert_eq!(Day03.part_one(&map).unwrap(), 7);
+        assert_eq!(Day03.part_two(&map).unwrap(), 336);
+        let puzzle = survey(&map, &SLOPES).unwrap();
+        let counts: Vec<u32> = puzzle.counts.iter().map(|c| c.trees).collect();
+        assert_eq!(counts, vec![2, 7, 3, 4, 2]);
+        // 11 slopes hitting 64 trees each overflow, the counts are kept
+        let input = Input::from_text(&"#\n".repeat(65), Day03.read_options());
+        let map = Day03.parse(&input).unwrap();
+        let wide = survey(&map, &[Slope::new(0, 1); 11]).unwrap();
+        assert_eq!(wide.product, None);
+        assert!(wide.counts.iter().all(|c| c.trees == 64));
+    }
+
+    #[test]
+    fn test_slopes() {
+        let map = Day03
+            .parse(&Input::from_text(
+                include_str!("../testdata/small.txt"),
+                Day03.read_options(),
+            ))
+            .unwrap();
+        assert_eq!((map.width(), map.height()), (11, 11));
+        // moving 3 left is moving 8 right on a pattern 11 wide
+        assert_eq!(map.count_trees(Slope::new(-3, 1)).unwrap(), 3);
+        assert_eq!(map.count_trees(Slope::new(-3 + 11, 1)).unwrap(), 3);
+        assert_eq!(map.count_trees(Slope::new(3 + 22, 1)).unwrap(), 7);
+        assert_eq!(map.count_trees(Slope::new(0, 1)).unwrap(), 3);
+        assert_eq!(map.count_trees(Slope::new(1, 20)).unwrap(), 0);
+        assert!(map.count_trees(Slope::new(1, 0)).is_err());
+        assert_eq!("-1, 2".parse::<Slope>().unwrap(), Slope::new(-1, 2));
+        assert!("3".parse::<Slope>().is_err());
+        assert!("3,0".parse::<Slope>().is_err());
+        assert!("3,-1".parse::<Slope>().is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let input = Input::from_text("..#\n.#\n", Day03.read_options());
+        assert!(Day03.parse(&input).is_err());
+        let input = Input::from_text("", Day03.read_options());
+        assert!(Day03.parse(&input).is_err());
+        let input = Input::from_text("..#\n.X.\n", Day03.read_options());
+        let err = Day03.parse(&input).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(2));
+        // lines with no squares are not a map
+        let input = Input::from_text("\n\n", ReadOptions::default());
+        assert_eq!(
+            TreeMap::parse(input.lines()).unwrap_err().to_string(),
+            "line 1, column 1: validation error: bad input - the map has no columns"
+        );
+    }
+
+    fn small(boundary: Boundary) -> TreeMap {
+        let input = Input::from_text(include_str!("../testdata/small.txt"), Day03.read_options());
+        Day03.parse(&input).unwrap().with_boundary(boundary)
+    }
+
+    // a map with rocks costing 5
+    fn rocky(boundary: Boundary) -> TreeMap {
+        let input = Input::from_text(".#R.\nR..#\n.R#.\n", Day03.read_options());
+        let legend = ".=open,#=tree:1,R=rock:5".parse().unwrap();
+        TreeMap::parse_with(input.lines(), legend)
+            .unwrap()
+            .with_boundary(boundary)
+    }
+
+    #[test]
+    fn test_legend() {
+        assert_eq!(
+            ".=open,#=tree:1".parse::<Legend>().unwrap(),
+            Legend::default()
+        );
+        assert!("ab=x".parse::<Legend>().is_err());
+        assert!("#=tree:x".parse::<Legend>().is_err());
+        assert!("#=".parse::<Legend>().is_err());
+        let map = rocky(Boundary::Wrap);
+        assert_eq!(map.terrain(1, 4).0, 'R');
+        assert_eq!(map.terrain(0, 2).1.name, "rock");
+        let count = |right| map.count(Slope::new(right, 1)).unwrap();
+        assert_eq!((count(0).trees, count(0).cost), (1, 5));
+        assert_eq!((count(-1).trees, count(-1).cost), (2, 2));
+        assert_eq!(map.cost(Slope::new(0, 1)).unwrap(), 5);
+        let input = Input::from_text(".#\n.R\n", Day03.read_options());
+        let err = TreeMap::parse(input.lines()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: parse error: bad input - illegal character 'R' not in the legend"
+        );
+        // columns count characters, not bytes
+        let input = Input::from_text("é.\n.é\n", Day03.read_options());
+        let legend = ".=open,é=ice:2".parse().unwrap();
+        let map = TreeMap::parse_with(input.lines(), legend).unwrap();
+        assert_eq!((map.width(), map.cost(Slope::new(1, 1)).unwrap()), (2, 2));
+    }
+
+    #[test]
+    fn test_boundary() {
+        let count = |boundary, right| {
+            let c = rocky(boundary).count(Slope::new(right, 1)).unwrap();
+            (c.trees, c.cost)
+        };
+        assert_eq!(count(Boundary::Clamp, -1), (1, 5));
+        assert_eq!(count(Boundary::Clamp, 3), (1, 1));
+        assert_eq!(count(Boundary::Stop, -1), (0, 0));
+        assert_eq!(count(Boundary::Stop, 2), (0, 0));
+        assert_eq!(count(Boundary::Stop, 3), (1, 1));
+        let path: Vec<(usize, isize)> = small(Boundary::Stop)
+            .path(Slope::new(3, 1))
+            .unwrap()
+            .collect();
+        assert_eq!(path, vec![(1, 3), (2, 6), (3, 9)]);
+        let path: Vec<(usize, isize)> = small(Boundary::Clamp)
+            .path(Slope::new(4, 2))
+            .unwrap()
+            .collect();
+        assert_eq!(path, vec![(2, 4), (4, 8), (6, 10), (8, 10), (10, 10)]);
+        // the steepest slopes either way, whose columns overflow
+        let trees = |boundary, right| small(boundary).count_trees(Slope::new(right, 1)).unwrap();
+        for &right in [isize::MAX, isize::MIN].iter() {
+            let wrapped = right.rem_euclid(11);
+            assert_eq!(trees(Boundary::Wrap, right), trees(Boundary::Wrap, wrapped));
+            let edge = right.signum() * 11;
+            assert_eq!(trees(Boundary::Clamp, right), trees(Boundary::Clamp, edge));
+            assert_eq!(trees(Boundary::Stop, right), 0);
+        }
+        assert_eq!("stop".parse::<Boundary>().unwrap(), Boundary::Stop);
+        assert!("bounce".parse::<Boundary>().is_err());
+    }
+
+    #[test]
+    fn test_best_slope() {
+        // every slope counted one by one, in the order of the tie-break
+        let brute =
+            |map: &TreeMap, right: RangeInclusive<isize>, down: RangeInclusive<usize>, goal| {
+                let mut all: Vec<(u64, usize, isize)> = Vec::new();
+                for d in down {
+                    for r in right.clone() {
+                        all.push((map.cost(Slope::new(r, d)).unwrap(), d, r));
+                    }
+                }
+                let (_, d, r) = match goal {
+                    Goal::Fewest => *all.iter().min().unwrap(),
+                    Goal::Most => *all
+                        .iter()
+                        .max_by_key(|&&(c, d, r)| (c, std::cmp::Reverse((d, r))))
+                        .unwrap(),
+                };
+                map.count(Slope::new(r, d)).unwrap()
+            };
+        for &boundary in [Boundary::Wrap, Boundary::Clamp, Boundary::Stop].iter() {
+            for map in [small(boundary), rocky(boundary)].iter() {
+                for (right, down) in [
+                    (1..=7, 1..=2),
+                    (-30..=30, 1..=12),
+                    (5..=5, 3..=20),
+                    (-2..=40, 2..=2),
+                    (20..=30, 1..=2),
+                    (-30..=-20, 1..=2),
+                    (isize::MAX - 7..=isize::MAX, 1..=1),
+                    (isize::MIN..=isize::MIN + 20, 1..=2),
+                ]
+                .iter()
+                {
+                    for &goal in [Goal::Fewest, Goal::Most].iter() {
+                        assert_eq!(
+                            best_slope(map, right.clone(), down.clone(), goal).unwrap(),
+                            brute(map, right.clone(), down.clone(), goal),
+                            "{:?} {:?} {:?} {:?}",
+                            boundary,
+                            right,
+                            down,
+                            goal
+                        );
+                    }
+                }
+            }
+        }
+        let map = small(Boundary::Wrap);
+        let best = best_slope(&map, 1..=7, 1..=1, Goal::Most).unwrap();
+        assert_eq!((best.slope, best.trees), (Slope::new(3, 1), 7));
+        let empty = RangeInclusive::new(1, 0);
+        assert!(best_slope(&map, empty, 1..=1, Goal::Most).is_err());
+        assert!(best_slope(&map, 1..=3, 0..=1, Goal::Most).is_err());
+    }
+}
@@ -0,0 +1,338 @@
+//! Render mode: the map with the path of a slope drawn over it, `O` for the
+//! open squares and `X` for the trees hit, as text and optionally as an SVG
+//! or PPM image.
+//!
+//! `day03 render [--slope R,D] [--svg PATH] [--ppm PATH] [--scale N] [MAP OPTIONS] [inputfile|-]`
+
+use crate::slopes::{MapArgs, MAP_USAGE};
+use crate::{Boundary, Slope, TreeMap};
+use std::fs;
+use utils::{cli, Error, Result, Source};
+
+/// One square of a rendered map, with the character of the map for those
+/// off the path; trees are any terrain that costs something.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Square {
+    Open(char),
+    Tree(char),
+    /// An open square on the path.
+    OpenHit,
+    /// A tree on the path.
+    TreeHit,
+}
+
+impl Square {
+    pub fn to_char(self) -> char {
+        match self {
+            Square::Open(c) | Square::Tree(c) => c,
+            Square::OpenHit => 'O',
+            Square::TreeHit => 'X',
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Square::Open(_) => [255, 255, 255],
+            Square::Tree(_) => [34, 139, 34],
+            Square::OpenHit => [100, 149, 237],
+            Square::TreeHit => [220, 20, 60],
+        }
+    }
+}
+
+/// The widest render, in squares: paths reaching further than that across the
+/// repeated pattern are refused rather than drawn.
+pub const MAX_WIDTH: usize = 10_000;
+
+/// The map with a path over it, the repeated pattern expanded as far as the
+/// path reaches on either side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Render {
+    /// Column of the map (0 is the left edge of the input) of the first
+    /// rendered column, negative when the path goes left.
+    pub left: isize,
+    pub rows: Vec<Vec<Square>>,
+}
+
+impl TreeMap {
+    /// Renders the path of the slope from the top left corner.
+    pub fn render(&self, slope: Slope) -> Result<Render> {
+        let path = self.path(slope)?;
+        let last = self.width() as isize - 1;
+        // only a wrapping path goes past the edges, as far as its last step
+        let (left, right) = match self.boundary() {
+            Boundary::Wrap => {
+                let steps = (self.height() - 1) / slope.down;
+                match (steps as isize).checked_mul(slope.right) {
+                    Some(end) => (end.min(0), end.max(last)),
+                    None => return Err(too_wide(slope, None)),
+                }
+            }
+            _ => (0, last),
+        };
+        match right.checked_sub(left).and_then(|w| w.checked_add(1)) {
+            Some(width) if width as usize <= MAX_WIDTH => (),
+            width => return Err(too_wide(slope, width)),
+        }
+        let mut rows: Vec<Vec<Square>> = (0..self.height())
+            .map(|row| {
+                (left..=right)
+                    .map(|col| match self.terrain(row, col) {
+                        (c, terrain) if terrain.cost > 0 => Square::Tree(c),
+                        (c, _) => Square::Open(c),
+                    })
+                    .collect()
+            })
+            .collect();
+        for (row, col) in path {
+            let square = &mut rows[row][(col - left) as usize];
+            *square = match square {
+                Square::Tree(_) => Square::TreeHit,
+                _ => Square::OpenHit,
+            };
+        }
+        Ok(Render { left, rows })
+    }
+}
+
+// the error of a path spanning `width` columns, `None` when that does not
+// even fit in an isize
+fn too_wide(slope: Slope, width: Option<isize>) -> Error {
+    let width = match width {
+        Some(width) => width.to_string(),
+        None => format!("more than {}", isize::MAX),
+    };
+    Error::validation(format!(
+        "the path of {} spans {} columns, at most {} can be rendered",
+        slope, width, MAX_WIDTH
+    ))
+}
+
+impl Render {
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// One line of characters per row.
+    pub fn to_text(&self) -> String {
+        let mut out = String::with_capacity((self.width() + 1) * self.rows.len());
+        for row in self.rows.iter() {
+            out.extend(row.iter().map(|s| s.to_char()));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A binary PPM (P6) image, `scale` pixels per square.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (w, h) = (self.width() * scale, self.rows.len() * scale);
+        let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+        out.reserve(w * h * 3);
+        for row in self.rows.iter() {
+            for _ in 0..scale {
+                for square in row.iter() {
+                    for _ in 0..scale {
+                        out.extend_from_slice(&square.rgb());
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// An SVG image, `scale` pixels per square: a white background and one
+    /// rectangle per square that is not plain open ground.
+    pub fn to_svg(&self, scale: usize) -> String {
+        let (w, h) = (self.width() * scale, self.rows.len() * scale);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <rect width=\"{w}\" height=\"{h}\" fill=\"#ffffff\"/>\n",
+            w = w,
+            h = h
+        );
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, &square) in row.iter().enumerate() {
+                if let Square::Open(_) = square {
+                    continue;
+                }
+                let [r, g, b] = square.rgb();
+                out.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" fill=\"#{:02x}{:02x}{:02x}\"/>\n",
+                    x * scale,
+                    y * scale,
+                    r,
+                    g,
+                    b,
+                    s = scale
+                ));
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+struct Args {
+    slope: Slope,
+    svg: Option<String>,
+    ppm: Option<String>,
+    scale: usize,
+    map: MapArgs,
+    source: Source,
+}
+
+const USAGE: &str =
+    "usage: day03 render [--slope R,D] [--svg PATH] [--ppm PATH] [--scale N] [--legend SPEC]
+                    [--boundary MODE] [inputfile|-]
+prints the map with the path of the slope (3,1 by default), O for open squares
+and X for trees hit; --svg and --ppm also write it as an image, N pixels per
+square (4 by default)";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut slope = Slope::new(3, 1);
+    let mut svg = None;
+    let mut ppm = None;
+    let mut scale = 4;
+    let mut map = MapArgs::default();
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--slope" => {
+                let value = cli::option_value(arg, &mut it)?;
+                slope = value.parse::<Slope>().map_err(|e| e.to_string())?;
+            }
+            "--svg" => svg = Some(cli::option_value(arg, &mut it)?.to_string()),
+            "--ppm" => ppm = Some(cli::option_value(arg, &mut it)?.to_string()),
+            "--scale" => scale = cli::positive_arg(arg, cli::option_value(arg, &mut it)?)?,
+            opt if map.parse_arg(opt, &mut it)? => (),
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        slope,
+        svg,
+        ppm,
+        scale,
+        map,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn write(path: &str, contents: &[u8]) -> Result<()> {
+    fs::write(path, contents).map_err(|e| Error::from(e).in_file(path))
+}
+
+fn run_render(args: &Args) -> Result<String> {
+    let map = args.map.load(&args.source)?;
+    let render = map.render(args.slope)?;
+    if let Some(path) = &args.svg {
+        write(path, render.to_svg(args.scale).as_bytes())?;
+    }
+    if let Some(path) = &args.ppm {
+        write(path, &render.to_ppm(args.scale))?;
+    }
+    Ok(render.to_text())
+}
+
+/// Entry point of `day03 render`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(
+        args,
+        parse_args,
+        run_render,
+        &format!("{}\n{}", USAGE, MAP_USAGE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use utils::{Input, Solution};
+
+    fn map() -> TreeMap {
+        let input = Input::from_text(include_str!("../testdata/small.txt"), Day03.read_options());
+        Day03.parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let render = map().render(Slope::new(3, 1)).unwrap();
+        assert_eq!(render.left, 0);
+        assert_eq!(render.width(), 31);
+        let text = render.to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        // as drawn in the puzzle, cut where the path ends
+        assert_eq!(lines[0], "..##.........##.........##.....");
+        assert_eq!(lines[1], "#..O#...#..#...#...#..#...#...#");
+        assert_eq!(lines[2], ".#....X..#..#....#..#..#....#..");
+        assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...X");
+        let hits = text.matches('X').count();
+        assert_eq!(hits as u32, map().count_trees(Slope::new(3, 1)).unwrap());
+    }
+
+    #[test]
+    fn test_render_left() {
+        let render = map().render(Slope::new(-1, 2)).unwrap();
+        assert_eq!(render.left, -5);
+        assert_eq!(render.width(), 16);
+        let text = render.to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], ".......##.......");
+        assert_eq!(lines[2], "#..#O.#....#..#.");
+        assert_eq!(lines[10], "O.#.#.#..#...#.#");
+        // a path shorter than the map renders the map as is
+        let render = map().render(Slope::new(1, 20)).unwrap();
+        assert_eq!((render.left, render.width()), (0, 11));
+        assert!(!render.to_text().contains(&['O', 'X'][..]));
+        // a path too wide to draw is refused before it is walked
+        let err = map().render(Slope::new(1_000_000_000, 1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "validation error: the path of 1000000000,1 spans 10000000001 columns, at most 10000 can be rendered"
+        );
+        let err = map()
+            .render(Slope::new(5_000_000_000_000_000_000, 1))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "validation error: the path of 5000000000000000000,1 spans more than 9223372036854775807 columns, at most 10000 can be rendered"
+        );
+        assert!(map().render(Slope::new(isize::MIN, 1)).is_err());
+        // the last step fits in an isize, the span across the map does not
+        assert!(map().render(Slope::new(isize::MIN / 10, 1)).is_err());
+        assert!(map().render(Slope::new(-990, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_images() {
+        let render = map().render(Slope::new(3, 1)).unwrap();
+        let ppm = render.to_ppm(2);
+        let header = b"P6\n62 22\n255\n";
+        assert_eq!(&ppm[..header.len()], &header[..]);
+        assert_eq!(ppm.len(), header.len() + 62 * 22 * 3);
+        let svg = render.to_svg(2);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"62\" height=\"22\"")
+        );
+        assert_eq!(svg.matches("#dc143c").count(), 7);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_render_legend() {
+        let input = Input::from_text(".#R.\nR..#\n.R#.\n", Day03.read_options());
+        let legend = ".=open,#=tree:1,R=rock:5".parse().unwrap();
+        let map = TreeMap::parse_with(input.lines(), legend).unwrap();
+        let render = map.render(Slope::new(0, 1)).unwrap();
+        assert_eq!(render.to_text(), ".#R.\nX..#\nOR#.\n");
+        // without wrapping the path stays within the map
+        let map = map.with_boundary(Boundary::Clamp);
+        let render = map.render(Slope::new(-1, 1)).unwrap();
+        assert_eq!(render.to_text(), ".#R.\nX..#\nOR#.\n");
+        let map = map.with_boundary(Boundary::Stop);
+        let render = map.render(Slope::new(3, 1)).unwrap();
+        assert_eq!(render.to_text(), ".#R.\nR..X\n.R#.\n");
+    }
+}
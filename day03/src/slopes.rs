@@ -0,0 +1,288 @@
+//! Slopes mode: the tree count of every given slope and their product, and
+//! the search for the best slope within bounds.
+//!
+//! `day03 slopes [--slope R,D]... [MAP OPTIONS] [--format text|json] [inputfile|-]`
+//!
+//! `day03 best --right MIN..MAX --down MIN..MAX [--most] [MAP OPTIONS] [--format text|json] [inputfile|-]`
+//!
+//! The map options, shared with render mode, are `--legend SPEC` (see
+//! [`Legend`]) and `--boundary wrap|clamp|stop`.
+
+use crate::{
+    best_slope, survey, Boundary, Day03, Goal, Legend, Slope, SlopeCount, Survey, TreeMap, SLOPES,
+};
+use serde_json::{json, Value};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use utils::cli::{self, Format};
+use utils::{Input, Result, Solution, Source};
+
+// the map options of every mode
+#[derive(Default)]
+pub(crate) struct MapArgs {
+    legend: Legend,
+    boundary: Boundary,
+}
+
+pub(crate) const MAP_USAGE: &str =
+    "--legend: the characters of the map, e.g. '.=open,#=tree:1,R=rock:5'
+--boundary: wrap (default), clamp or stop at the left and right edges";
+
+impl MapArgs {
+    // takes the argument, and its value from the iterator, if it is a map
+    // option
+    pub(crate) fn parse_arg<'a>(
+        &mut self,
+        arg: &str,
+        it: &mut impl Iterator<Item = &'a String>,
+    ) -> std::result::Result<bool, String> {
+        match arg {
+            "--legend" => {
+                let value = cli::option_value(arg, it)?;
+                self.legend = value.parse().map_err(|e: utils::Error| e.to_string())?;
+            }
+            "--boundary" => {
+                let value = cli::option_value(arg, it)?;
+                self.boundary = value.parse().map_err(|e: utils::Error| e.to_string())?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn load(&self, source: &Source) -> Result<TreeMap> {
+        let input = Input::read(source, Day03.read_options())?;
+        let map = TreeMap::parse_with(input.lines(), self.legend.clone())
+            .map_err(|e| e.in_file(input.name()))?;
+        Ok(map.with_boundary(self.boundary))
+    }
+}
+
+/// The survey as a table with one row per slope and the product below.
+pub fn to_text(survey: &Survey) -> String {
+    let mut out = format!(
+        "{:>6} {:>5} {:>6} {:>6}\n",
+        "right", "down", "trees", "cost"
+    );
+    for c in survey.counts.iter() {
+        out.push_str(&format!(
+            "{:>6} {:>5} {:>6} {:>6}\n",
+            c.slope.right, c.slope.down, c.trees, c.cost
+        ));
+    }
+    match survey.product {
+        Some(product) => out.push_str(&format!("product: {}\n", product)),
+        None => out.push_str("product: overflow, more than 64 bits\n"),
+    }
+    out
+}
+
+/// The survey as `{"slopes": [{"right", "down", "trees", "cost"}], "product", "overflow"}`,
+/// the product `null` when it overflows.
+pub fn to_json(survey: &Survey) -> Value {
+    let slopes: Vec<Value> = survey.counts.iter().map(count_json).collect();
+    json!({
+        "slopes": slopes,
+        "product": survey.product,
+        "overflow": survey.product.is_none(),
+    })
+}
+
+fn count_json(c: &SlopeCount) -> Value {
+    json!({
+        "right": c.slope.right,
+        "down": c.slope.down,
+        "trees": c.trees,
+        "cost": c.cost,
+    })
+}
+
+struct Args {
+    slopes: Vec<Slope>,
+    map: MapArgs,
+    format: Format,
+    source: Source,
+}
+
+const USAGE: &str = "usage: day03 slopes [--slope R,D]... [--legend SPEC] [--boundary MODE]
+                    [--format text|json] [inputfile|-]
+R is the move right (negative for left) for every D rows down;
+the five slopes of part two by default";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut slopes = Vec::new();
+    let mut map = MapArgs::default();
+    let mut format = Format::Text;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--slope" => {
+                let value = cli::option_value(arg, &mut it)?;
+                slopes.push(value.parse::<Slope>().map_err(|e| e.to_string())?);
+            }
+            "--format" => format = Format::parse_arg(cli::option_value(arg, &mut it)?)?,
+            opt if map.parse_arg(opt, &mut it)? => (),
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    if slopes.is_empty() {
+        slopes = SLOPES.to_vec();
+    }
+    Ok(Args {
+        slopes,
+        map,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_slopes(args: &Args) -> Result<String> {
+    let map = args.map.load(&args.source)?;
+    let survey = survey(&map, &args.slopes)?;
+    Ok(match args.format {
+        Format::Text => to_text(&survey),
+        Format::Json => format!("{}\n", to_json(&survey)),
+    })
+}
+
+/// Entry point of `day03 slopes`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(
+        args,
+        parse_args,
+        run_slopes,
+        &format!("{}\n{}", USAGE, MAP_USAGE),
+    )
+}
+
+/// Parses a range `MIN..MAX`, both included, or a single value.
+pub fn parse_range<T: FromStr>(arg: &str) -> std::result::Result<RangeInclusive<T>, String> {
+    let bad = || format!("bad range '{}' - expect <min>..<max>", arg);
+    let (min, max) = arg.split_once("..").unwrap_or((arg, arg));
+    let min = min.trim().parse::<T>().map_err(|_| bad())?;
+    let max = max.trim().parse::<T>().map_err(|_| bad())?;
+    Ok(min..=max)
+}
+
+struct BestArgs {
+    right: RangeInclusive<isize>,
+    down: RangeInclusive<usize>,
+    goal: Goal,
+    map: MapArgs,
+    format: Format,
+    source: Source,
+}
+
+const BEST_USAGE: &str =
+    "usage: day03 best --right MIN..MAX --down MIN..MAX [--most] [--legend SPEC]
+                  [--boundary MODE] [--format text|json] [inputfile|-]
+finds the slope with the lowest cost, or the highest with --most, by default
+the fewest or most trees; ties go to the smallest down, then the smallest right";
+
+fn parse_best_args(args: &[String]) -> std::result::Result<BestArgs, String> {
+    let mut right = None;
+    let mut down = None;
+    let mut goal = Goal::Fewest;
+    let mut map = MapArgs::default();
+    let mut format = Format::Text;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--right" => right = Some(parse_range(cli::option_value(arg, &mut it)?)?),
+            "--down" => down = Some(parse_range(cli::option_value(arg, &mut it)?)?),
+            "--most" => goal = Goal::Most,
+            "--format" => format = Format::parse_arg(cli::option_value(arg, &mut it)?)?,
+            opt if map.parse_arg(opt, &mut it)? => (),
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(BestArgs {
+        right: right.ok_or("--right is required")?,
+        down: down.ok_or("--down is required")?,
+        goal,
+        map,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_best(args: &BestArgs) -> Result<String> {
+    let map = args.map.load(&args.source)?;
+    let best = best_slope(&map, args.right.clone(), args.down.clone(), args.goal)?;
+    Ok(match args.format {
+        Format::Text => format!(
+            "slope {}, trees hit: {}, cost: {}\n",
+            best.slope, best.trees, best.cost
+        ),
+        Format::Json => format!("{}\n", count_json(&best)),
+    })
+}
+
+/// Entry point of `day03 best`, returns the exit status.
+pub fn best_main(args: &[String]) -> i32 {
+    cli::run_subcommand(
+        args,
+        parse_best_args,
+        run_best,
+        &format!("{}\n{}", BEST_USAGE, MAP_USAGE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn survey() -> Survey {
+        Survey {
+            counts: vec![
+                SlopeCount {
+                    slope: Slope::new(3, 1),
+                    trees: 7,
+                    cost: 7,
+                },
+                SlopeCount {
+                    slope: Slope::new(-1, 2),
+                    trees: 12,
+                    cost: 20,
+                },
+            ],
+            product: Some(84),
+        }
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            to_text(&survey()),
+            " right  down  trees   cost\n     3     1      7      7\n    -1     2     12     20\nproduct: 84\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json = to_json(&survey());
+        assert_eq!(json["slopes"][1]["right"], -1);
+        assert_eq!(json["slopes"][1]["trees"], 12);
+        assert_eq!(json["slopes"][1]["cost"], 20);
+        assert_eq!(json["product"], 84);
+        assert_eq!(json["overflow"], false);
+        let overflow = Survey {
+            product: None,
+            ..survey()
+        };
+        assert!(to_text(&overflow).ends_with("\nproduct: overflow, more than 64 bits\n"));
+        let json = to_json(&overflow);
+        assert!(json["product"].is_null());
+        assert_eq!(json["overflow"], true);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range::<isize>("-3..5"), Ok(-3..=5));
+        assert_eq!(parse_range::<usize>("2"), Ok(2..=2));
+        assert!(parse_range::<usize>("-1..2").is_err());
+        assert!(parse_range::<isize>("1..").is_err());
+    }
+}
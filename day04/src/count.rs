@@ -0,0 +1,66 @@
+//! Count mode: the answers of both parts under a schema of choice, so that
+//! the rules can change without recompiling.
+//!
+//! `day04 count [--schema PATH] [--strict|--lenient] [inputfile|-]`
+
+use crate::count_valid;
+use crate::record::Mode;
+use crate::schema::Schema;
+use crate::Day04;
+use utils::{cli, Input, Result, Solution, Source};
+
+struct Args {
+    schema: Option<Source>,
+    mode: Mode,
+    source: Source,
+}
+
+const USAGE: &str = "usage: day04 count [--schema PATH] [--strict|--lenient] [inputfile|-]
+counts the complete and the valid passports under the schema,
+the rules of the puzzle (day04/schema.txt) by default;
+malformed tokens, unknown and duplicate keys are errors with --strict,
+warnings with --lenient (the default)";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut schema = None;
+    let mut mode = Mode::default();
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--schema" => schema = Some(Source::from_arg(cli::option_value(arg, &mut it)?)),
+            "--strict" => mode = Mode::Strict,
+            "--lenient" => mode = Mode::Lenient,
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        schema,
+        mode,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_count(args: &Args) -> Result<String> {
+    let schema = match &args.schema {
+        Some(source) => Schema::read(source)?,
+        None => Schema::default(),
+    };
+    let input = Input::read(&args.source, Day04.read_options())?;
+    let tag = |e: utils::Error| e.in_file(input.name());
+    let mut warnings = Vec::new();
+    let complete =
+        count_valid(input.lines(), &schema, args.mode, false, &mut warnings).map_err(tag)?;
+    // the warnings are the same for both parts
+    let valid =
+        count_valid(input.lines(), &schema, args.mode, true, &mut Vec::new()).map_err(tag)?;
+    for warning in warnings {
+        eprintln!("{}", warning.in_file(input.name()));
+    }
+    Ok(format!("part one: {}\npart two: {}\n", complete, valid))
+}
+
+/// Entry point of `day04 count`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(args, parse_args, run_count, USAGE)
+}
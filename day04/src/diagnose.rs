@@ -0,0 +1,343 @@
+//! Diagnose mode: for every passport record, where it starts, which
+//! required fields it lacks and which fields break their rule.
+//!
+//! `day04 diagnose [--schema PATH] [--strict|--lenient] [--all] [--format text|json] [inputfile|-]`
+//!
+//! The records are parsed like in `count`, so that the valid ones are those
+//! counted by part two: the tokens that are not fields of the schema are
+//! errors or warnings depending on the mode, see [`Mode`].
+
+use crate::record::{records, Mode, Record};
+use crate::schema::Schema;
+use crate::Day04;
+use serde_json::{json, Value};
+use utils::cli::{self, Format};
+use utils::{Input, Line, Result, Solution, Source, Warning};
+
+/// A field of a record that breaks its rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub line: usize,
+    /// Column of the value, where the validation errors of `count` point.
+    pub column: usize,
+    pub field: String,
+    pub value: String,
+    /// What the field should have been, e.g. `a year in 1920-2002`.
+    pub rule: String,
+}
+
+/// What is wrong with a passport record, if anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnosis {
+    /// Line number of the first line of the record.
+    pub line: usize,
+    /// The required fields of the schema the record does not have.
+    pub missing: Vec<String>,
+    pub failures: Vec<Failure>,
+}
+
+impl Diagnosis {
+    /// Whether the record is valid in the sense of part two.
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.failures.is_empty()
+    }
+}
+
+/// Diagnoses every record, in order, parsed in the mode.
+pub fn diagnose<'a, I>(
+    it: I,
+    schema: &Schema,
+    mode: Mode,
+    warnings: &mut Vec<Warning>,
+) -> Result<Vec<Diagnosis>>
+where
+    I: IntoIterator<Item = &'a Line>,
+{
+    records(it)
+        .map(|r| Ok(diagnose_record(&mode.apply(r, schema, warnings)?, schema)))
+        .collect()
+}
+
+/// Diagnoses a record returned by [`Mode::apply`].
+pub fn diagnose_record(record: &Record, schema: &Schema) -> Diagnosis {
+    let mut seen = vec![false; schema.fields().len()];
+    let mut failures = Vec::new();
+    for (i, field, f, value) in record.schema_fields(schema) {
+        seen[i] = true;
+        if !field.kind.accepts(value) {
+            failures.push(Failure {
+                line: f.line,
+                column: f.value_column(),
+                field: f.key.clone(),
+                value: value.to_string(),
+                rule: field.kind.to_string(),
+            });
+        }
+    }
+    let missing = schema
+        .fields()
+        .iter()
+        .zip(seen)
+        .filter(|(field, seen)| field.required && !seen)
+        .map(|(field, _)| field.name.clone())
+        .collect();
+    Diagnosis {
+        line: record.line,
+        missing,
+        failures,
+    }
+}
+
+/// The diagnoses as text, a line per record and an indented line per
+/// problem.
+pub fn to_text(diagnoses: &[Diagnosis]) -> String {
+    let mut out = String::new();
+    for d in diagnoses.iter() {
+        if d.is_valid() {
+            out.push_str(&format!("record at line {}: valid\n", d.line));
+            continue;
+        }
+        out.push_str(&format!("record at line {}: invalid\n", d.line));
+        if !d.missing.is_empty() {
+            out.push_str(&format!("  missing {}\n", d.missing.join(", ")));
+        }
+        for f in d.failures.iter() {
+            out.push_str(&format!(
+                "  line {}, column {}: {}:{} is not {}\n",
+                f.line, f.column, f.field, f.value, f.rule
+            ));
+        }
+    }
+    out
+}
+
+/// The diagnoses as a JSON array,
+/// `[{"line", "valid", "missing", "failures": [{"line", "column", "field", "value", "rule"}]}]`.
+pub fn to_json(diagnoses: &[Diagnosis]) -> Value {
+    let records: Vec<Value> = diagnoses
+        .iter()
+        .map(|d| {
+            let failures: Vec<Value> = d
+                .failures
+                .iter()
+                .map(|f| {
+                    json!({
+                        "line": f.line,
+                        "column": f.column,
+                        "field": f.field,
+                        "value": f.value,
+                        "rule": f.rule,
+                    })
+                })
+                .collect();
+            json!({
+                "line": d.line,
+                "valid": d.is_valid(),
+                "missing": d.missing,
+                "failures": failures,
+            })
+        })
+        .collect();
+    Value::Array(records)
+}
+
+struct Args {
+    schema: Option<Source>,
+    mode: Mode,
+    all: bool,
+    format: Format,
+    source: Source,
+}
+
+const USAGE: &str = "usage: day04 diagnose [--schema PATH] [--strict|--lenient] [--all]
+                      [--format text|json] [inputfile|-]
+lists the invalid passport records with their missing fields and the fields
+breaking their rule; --all also lists the valid records; malformed tokens,
+unknown and duplicate keys are errors with --strict, warnings with --lenient
+(the default)";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut schema = None;
+    let mut mode = Mode::default();
+    let mut all = false;
+    let mut format = Format::Text;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--schema" => schema = Some(Source::from_arg(cli::option_value(arg, &mut it)?)),
+            "--strict" => mode = Mode::Strict,
+            "--lenient" => mode = Mode::Lenient,
+            "--all" => all = true,
+            "--format" => format = Format::parse_arg(cli::option_value(arg, &mut it)?)?,
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        schema,
+        mode,
+        all,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_diagnose(args: &Args) -> Result<String> {
+    let schema = match &args.schema {
+        Some(source) => Schema::read(source)?,
+        None => Schema::default(),
+    };
+    let input = Input::read(&args.source, Day04.read_options())?;
+    let mut warnings = Vec::new();
+    let mut diagnoses = diagnose(input.lines(), &schema, args.mode, &mut warnings)
+        .map_err(|e| e.in_file(input.name()))?;
+    for warning in warnings {
+        eprintln!("{}", warning.in_file(input.name()));
+    }
+    if !args.all {
+        diagnoses.retain(|d| !d.is_valid());
+    }
+    Ok(match args.format {
+        Format::Text => to_text(&diagnoses),
+        Format::Json => format!("{}\n", to_json(&diagnoses)),
+    })
+}
+
+/// Entry point of `day04 diagnose`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(args, parse_args, run_diagnose, USAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnoses(text: &str) -> Vec<Diagnosis> {
+        let input = Input::from_text(text, Day04.read_options());
+        diagnose(
+            input.lines(),
+            &Schema::default(),
+            Mode::Lenient,
+            &mut Vec::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let d = diagnoses(include_str!("../testdata/invalid.txt"));
+        assert_eq!(d.len(), 4);
+        assert_eq!(
+            d.iter().map(|d| d.line).collect::<Vec<_>>(),
+            vec![1, 4, 8, 11]
+        );
+        assert!(d.iter().all(|d| !d.is_valid() && d.missing.is_empty()));
+        assert_eq!(
+            d[0].failures[0],
+            Failure {
+                line: 1,
+                column: 5,
+                field: "eyr".to_string(),
+                value: "1972".to_string(),
+                rule: "a year in 2020-2030".to_string(),
+            }
+        );
+        let hgt = &d[0].failures[1];
+        assert_eq!((hgt.line, hgt.column, hgt.field.as_str()), (2, 25, "hgt"));
+        assert_eq!(d[3].failures.len(), 7);
+        let d = diagnoses(include_str!("../testdata/small.txt"));
+        assert!(d[0].is_valid());
+        assert_eq!(d[1].missing, vec!["hgt"]);
+        assert_eq!(d[3].missing, vec!["byr"]);
+        // the tokens left out by the mode are warnings, not failures
+        let input = Input::from_text("\n\nbyr:1980 foo:1 pid byr:1980\n", Day04.read_options());
+        let mut warnings = Vec::new();
+        let d = diagnose(
+            input.lines(),
+            &Schema::default(),
+            Mode::Lenient,
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(d[0].line, 3);
+        assert_eq!(d[0].missing.len(), 6);
+        assert!(d[0].failures.is_empty());
+        assert_eq!(warnings.len(), 3);
+        let err = diagnose(
+            input.lines(),
+            &Schema::default(),
+            Mode::Strict,
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 10: parse error: unknown key: foo"
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        // diagnose and count agree in both modes
+        let text = [
+            "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327 xyz:1 byr:1800",
+            "",
+            "hcl:#cfa07d eyr:2025 pid:166559648 iyr:2011 ecl:brn hgt:59in",
+            "",
+            include_str!("../testdata/invalid.txt"),
+        ];
+        let input = Input::from_text(&text.join("\n"), Day04.read_options());
+        let schema = Schema::default();
+        let count = |mode: Mode, part2: bool| {
+            crate::count_valid(input.lines(), &schema, mode, part2, &mut Vec::new())
+        };
+        let d = diagnose(input.lines(), &schema, Mode::Lenient, &mut Vec::new()).unwrap();
+        assert!(d[0].is_valid());
+        let valid = d.iter().filter(|d| d.is_valid()).count() as u32;
+        let complete = d.iter().filter(|d| d.missing.is_empty()).count() as u32;
+        assert_eq!(valid, count(Mode::Lenient, true).unwrap());
+        assert_eq!(complete, count(Mode::Lenient, false).unwrap());
+        let err = diagnose(input.lines(), &schema, Mode::Strict, &mut Vec::new()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            count(Mode::Strict, true).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        // the valid records are those counted by part two
+        let input = Input::from_text(include_str!("../testdata/input.txt"), Day04.read_options());
+        let batch = Day04.parse(&input).unwrap();
+        let valid = diagnose(
+            input.lines(),
+            &Schema::default(),
+            Mode::Lenient,
+            &mut Vec::new(),
+        )
+        .unwrap()
+        .iter()
+        .filter(|d| d.is_valid())
+        .count();
+        assert_eq!(valid as u32, Day04.part_two(&batch).unwrap());
+    }
+
+    #[test]
+    fn test_output() {
+        let d = diagnoses("hgt:170 byr:1980\n");
+        let text = to_text(&d);
+        assert!(text.starts_with(
+            "record at line 1: invalid\n  missing iyr, eyr, hcl, ecl, pid\n  line 1, column 5: hgt:170 is not"
+        ));
+        let json = to_json(&d);
+        assert_eq!(json[0]["valid"], false);
+        assert_eq!(json[0]["missing"][0], "iyr");
+        assert_eq!(json[0]["failures"][0]["column"], 5);
+        // the column of the validation error of part two
+        let input = Input::from_text("hgt:170 byr:1980\n", Day04.read_options());
+        let record = records(input.lines()).next().unwrap();
+        let err = crate::validate_record(&record, &Schema::default()).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(5));
+        assert_eq!(json[0]["failures"][0]["value"], "170");
+    }
+}
@@ -0,0 +1,195 @@
+//! Export mode: every passport record with its typed values, as CSV or JSON,
+//! for loading the batch into other tools.
+//!
+//! `day04 export [--schema PATH] [--strict|--lenient] [--format csv|json] [inputfile|-]`
+//!
+//! There is a column per field of the schema, but heights are `<name>_cm`
+//! and colours are `<name>_r`, `<name>_g` and `<name>_b` (`<name>_rgb`, an
+//! array, in JSON). Missing values and values breaking their rule are empty
+//! (`null` in JSON); `day04 diagnose` tells why.
+
+use crate::passport::{Passport, Value};
+use crate::record::{records, Mode};
+use crate::schema::{FieldDef, FieldType, Schema};
+use crate::Day04;
+use serde_json::{json, Map};
+use utils::cli::{self, csv_field, TableFormat};
+use utils::{Input, Result, Solution, Source};
+
+// the CSV columns of a field
+fn columns(field: &FieldDef) -> Vec<String> {
+    match field.kind {
+        FieldType::Height { .. } => vec![format!("{}_cm", field.name)],
+        FieldType::HexColour => ["r", "g", "b"]
+            .iter()
+            .map(|c| format!("{}_{}", field.name, c))
+            .collect(),
+        _ => vec![field.name.clone()],
+    }
+}
+
+/// The passports as CSV, with a header line.
+pub fn to_csv(schema: &Schema, passports: &[Passport]) -> String {
+    let mut header = vec!["line".to_string(), "valid".to_string()];
+    header.extend(schema.fields().iter().flat_map(columns));
+    let mut out = header.join(",");
+    out.push('\n');
+    for p in passports.iter() {
+        let mut row = vec![p.line.to_string(), p.valid.to_string()];
+        for (field, value) in schema.fields().iter().zip(p.values.iter()) {
+            match value {
+                Some(Value::Year(year)) => row.push(year.to_string()),
+                Some(Value::Height(cm)) => row.push(cm.to_string()),
+                Some(Value::Colour(rgb)) => row.extend(rgb.iter().map(u8::to_string)),
+                Some(Value::Text(text)) => row.push(csv_field(text)),
+                Some(Value::Invalid(_)) | None => {
+                    row.extend(columns(field).iter().map(|_| String::new()))
+                }
+            }
+        }
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// The passports as a JSON array of objects, keyed like the CSV columns.
+pub fn to_json(schema: &Schema, passports: &[Passport]) -> serde_json::Value {
+    let rows = passports
+        .iter()
+        .map(|p| {
+            let mut row = Map::new();
+            row.insert("line".to_string(), json!(p.line));
+            row.insert("valid".to_string(), json!(p.valid));
+            for (field, value) in schema.fields().iter().zip(p.values.iter()) {
+                let (key, value) = match value {
+                    Some(Value::Year(year)) => (field.name.clone(), json!(year)),
+                    Some(Value::Height(cm)) => (format!("{}_cm", field.name), json!(cm)),
+                    Some(Value::Colour(rgb)) => (format!("{}_rgb", field.name), json!(rgb)),
+                    Some(Value::Text(text)) => (field.name.clone(), json!(text)),
+                    Some(Value::Invalid(_)) | None => {
+                        let key = match field.kind {
+                            FieldType::Height { .. } => format!("{}_cm", field.name),
+                            FieldType::HexColour => format!("{}_rgb", field.name),
+                            _ => field.name.clone(),
+                        };
+                        (key, serde_json::Value::Null)
+                    }
+                };
+                row.insert(key, value);
+            }
+            serde_json::Value::Object(row)
+        })
+        .collect();
+    serde_json::Value::Array(rows)
+}
+
+struct Args {
+    schema: Option<Source>,
+    mode: Mode,
+    format: TableFormat,
+    source: Source,
+}
+
+const USAGE: &str =
+    "usage: day04 export [--schema PATH] [--strict|--lenient] [--format csv|json] [inputfile|-]
+writes every passport record with its values typed after the schema:
+heights in centimetres, colours as red, green and blue; malformed tokens,
+unknown and duplicate keys are errors with --strict, warnings with --lenient
+(the default)";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut schema = None;
+    let mut mode = Mode::default();
+    let mut format = TableFormat::Csv;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--schema" => schema = Some(Source::from_arg(cli::option_value(arg, &mut it)?)),
+            "--strict" => mode = Mode::Strict,
+            "--lenient" => mode = Mode::Lenient,
+            "--format" => format = TableFormat::parse_arg(cli::option_value(arg, &mut it)?)?,
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        schema,
+        mode,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_export(args: &Args) -> Result<String> {
+    let schema = match &args.schema {
+        Some(source) => Schema::read(source)?,
+        None => Schema::default(),
+    };
+    let input = Input::read(&args.source, Day04.read_options())?;
+    let mut warnings = Vec::new();
+    let mut passports = Vec::new();
+    for record in records(input.lines()) {
+        let record = args
+            .mode
+            .apply(record, &schema, &mut warnings)
+            .map_err(|e| e.in_file(input.name()))?;
+        passports.push(Passport::from_record(&record, &schema));
+    }
+    for warning in warnings {
+        eprintln!("{}", warning.in_file(input.name()));
+    }
+    Ok(match args.format {
+        TableFormat::Csv => to_csv(&schema, &passports),
+        TableFormat::Json => format!("{}\n", to_json(&schema, &passports)),
+    })
+}
+
+/// Entry point of `day04 export`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(args, parse_args, run_export, USAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passports(text: &str) -> Vec<Passport> {
+        let input = Input::from_text(text, Day04.read_options());
+        let schema = Schema::default();
+        records(input.lines())
+            .map(|r| Mode::Lenient.apply(r, &schema, &mut Vec::new()).unwrap())
+            .map(|r| Passport::from_record(&r, &schema))
+            .collect()
+    }
+
+    #[test]
+    fn test_csv() {
+        let text = "hgt:66in hcl:#623a2f byr:1937 ecl:brn\n\npid:0123 cid:\"a,b\" hcl:#zzz\n";
+        let csv = to_csv(&Schema::default(), &passports(text));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "line,valid,byr,iyr,eyr,hgt_cm,hcl_r,hcl_g,hcl_b,ecl,pid,cid"
+        );
+        assert_eq!(lines[1], "1,false,1937,,,167.64,98,58,47,brn,,");
+        assert_eq!(lines[2], "3,false,,,,,,,,,,\"\"\"a,b\"\"\"");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_json() {
+        let json = to_json(
+            &Schema::default(),
+            &passports(include_str!("../testdata/small.txt")),
+        );
+        assert_eq!(json.as_array().unwrap().len(), 4);
+        assert_eq!(json[0]["line"], 1);
+        assert_eq!(json[0]["valid"], true);
+        assert_eq!(json[0]["hgt_cm"], 183.0);
+        assert_eq!(json[0]["hcl_rgb"], json!([255, 255, 253]));
+        assert_eq!(json[1]["pid"], "028048884");
+        assert_eq!(json[1]["hgt_cm"], serde_json::Value::Null);
+        assert_eq!(json[3]["hgt_cm"], 149.86);
+    }
+}
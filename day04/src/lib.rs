@@ -0,0 +1,257 @@
+pub mod count;
+pub mod diagnose;
+pub mod export;
+pub mod passport;
+pub mod record;
+pub mod schema;
+pub mod stream;
+
+use record::{records, Mode, Record};
+use schema::Schema;
+use utils::{Input, Line, ReadOptions, Result, Solution, Trim, Warning};
+
+/// The puzzle under the default schema, parsing in lenient mode: tokens
+/// that are not fields of the schema are reported as warnings.
+pub struct Day04;
+
+/// The records of a batch as the puzzle reads them: under the default schema,
+/// in lenient mode, with the warnings about the tokens left out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    pub schema: Schema,
+    pub records: Vec<Record>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Batch {
+    /// Number of records with all the required fields, and in part two only
+    /// those whose fields all follow their rules.
+    pub fn count_valid(&self, part2: bool) -> u32 {
+        self.records
+            .iter()
+            .filter(|record| is_valid(record, &self.schema, part2))
+            .count() as u32
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed = Batch;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End)
+    }
+
+    fn parse(&self, input: &Input) -> Result<Batch> {
+        let schema = Schema::default();
+        let mut warnings = Vec::new();
+        let records = records(input.lines())
+            .map(|record| Mode::Lenient.apply(record, &schema, &mut warnings))
+            .collect::<Result<Vec<Record>>>()?;
+        Ok(Batch {
+            schema,
+            records,
+            warnings,
+        })
+    }
+
+    fn part_one(&self, batch: &Batch) -> Result<u32> {
+        Ok(batch.count_valid(false))
+    }
+
+    fn part_two(&self, batch: &Batch) -> Result<u32> {
+        Ok(batch.count_valid(true))
+    }
+
+    // the same for both parts, the records being parsed once
+    fn warnings(&self, batch: &Batch) -> Vec<Warning> {
+        batch.warnings.clone()
+    }
+}
+
+/// Counts the passports with all the required fields of the schema, and in
+/// part two only those whose fields all follow the rules of their types.
+/// The mode decides what happens to the tokens that are not fields of the
+/// schema, see [`Mode::apply`].
+pub fn count_valid<'a, I>(
+    it: I,
+    schema: &Schema,
+    mode: Mode,
+    part2: bool,
+    warnings: &mut Vec<Warning>,
+) -> Result<u32>
+where
+    I: IntoIterator<Item = &'a Line>,
+{
+    let mut count = 0;
+    for record in records(it) {
+        let record = mode.apply(record, schema, warnings)?;
+        if is_valid(&record, schema, part2) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// whether a record returned by Mode::apply counts in the part
+fn is_valid(record: &Record, schema: &Schema, part2: bool) -> bool {
+    let seen = if part2 {
+        validate_record(record, schema).ok()
+    } else {
+        Some(check_record(record, schema))
+    };
+    seen.is_some_and(|seen| schema.is_complete(&seen))
+}
+
+// part one processing: the fields of the record seen, indexed like the
+// schema fields
+fn check_record(record: &Record, schema: &Schema) -> Vec<bool> {
+    let mut seen = vec![false; schema.fields().len()];
+    for (i, _, _, _) in record.schema_fields(schema) {
+        seen[i] = true;
+    }
+    seen
+}
+
+// part two processing: the fields of the record seen, or the first field
+// breaking its rule
+fn validate_record(record: &Record, schema: &Schema) -> Result<Vec<bool>> {
+    let mut seen = vec![false; schema.fields().len()];
+    for (i, field, f, value) in record.schema_fields(schema) {
+        field
+            .check(value)
+            .map_err(|e| e.at_line(f.line).at_column(f.value_column()))?;
+        seen[i] = true;
+    }
+    Ok(seen)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    // the record of a single line, as parsed in strict mode
+    fn record(line: &str) -> Result<Record> {
+        let input = Input::from_text(line, Day04.read_options());
+        let record = records(input.lines()).next().unwrap();
+        Mode::Strict.apply(record, &Schema::default(), &mut Vec::new())
+    }
+
+    // the fields of the line seen by part two, one flag per schema field
+    fn validate(line: &str) -> Result<Vec<bool>> {
+        validate_record(&record(line)?, &Schema::default())
+    }
+
+    fn complete(seen: &[bool]) -> bool {
+        Schema::default().is_complete(seen)
+    }
+
+    #[test]
+    fn valid_key_value() {
+        assert!(validate("byr:2002").is_ok());
+        assert!(validate("hgt:60in").is_ok());
+        assert!(validate("hgt:190cm").is_ok());
+        assert!(validate("hcl:#123abc").is_ok());
+        assert!(validate("ecl:brn").is_ok());
+        assert!(validate("pid:000000001").is_ok());
+    }
+
+    #[test]
+    fn invalid_key_value() {
+        assert!(validate("byr:2003").is_err());
+        assert!(validate("hgt:190").is_err());
+        assert!(validate("hcl:#123abz").is_err());
+        assert!(validate("hcl:123abc").is_err());
+        assert!(validate("ecl:wat").is_err());
+        assert!(validate("pid:0123456789").is_err());
+    }
+
+    #[test]
+    fn valid_passports() {
+        // the flags are in the order of the schema, cid last
+        let inp01 = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+        let seen = validate(inp01).unwrap();
+        assert!(complete(&seen) && !seen[7]);
+        let inp02 =
+            "eyr:2029 ecl:blu cid:129 byr:1989 iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm";
+        assert_eq!(validate(inp02).unwrap(), vec![true; 8]);
+        let inp03 = "hcl:#888785 hgt:164cm byr:2001 iyr:2015 cid:88 pid:545766238 ecl:hzl eyr:2022";
+        assert_eq!(validate(inp03).unwrap(), vec![true; 8]);
+        let inp04 = "iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+        let seen = validate(inp04).unwrap();
+        assert!(complete(&seen) && !seen[7]);
+    }
+
+    #[test]
+    fn invalid_passports() {
+        let inputs = [
+            "eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+            "iyr:2019 hcl:#602927 eyr:1967 hgt:170cm ecl:grn pid:012533040 byr:1946",
+            "hcl:dab227 iyr:2012 ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277",
+            "hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007",
+        ];
+        for inp in inputs.iter() {
+            assert!(validate(inp).is_err());
+        }
+    }
+
+    #[test]
+    fn error_location() {
+        let err = record("ecl:gry xyz:1").unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(9));
+        let seen = check_record(&record("ecl:gry hgt:5").unwrap(), &Schema::default());
+        assert_eq!(seen.iter().filter(|&&seen| seen).count(), 2);
+        let err = validate("byr:1937 hgt:5").unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(14));
+    }
+
+    #[test]
+    fn custom_schema() {
+        let text = "byr required year 1900 2000\npid optional digits 3\n";
+        let schema = Schema::parse(&Input::from_text(text, ReadOptions::default())).unwrap();
+        let input = Input::from_text(
+            "byr:1950\n\nbyr:2010 pid:123\n\npid:123\n\nbyr:1990 pid:1234\n",
+            Day04.read_options(),
+        );
+        let count = |schema: &Schema, part2: bool| {
+            count_valid(input.lines(), schema, Mode::Strict, part2, &mut Vec::new())
+        };
+        assert_eq!(count(&schema, false).unwrap(), 3);
+        assert_eq!(count(&schema, true).unwrap(), 1);
+        // the default schema does not know these records
+        assert!(count(&Schema::default(), false).is_ok());
+    }
+
+    #[test]
+    fn modes() {
+        let input = Input::from_text(
+            "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327\n\
+             xyz:1\n\nbyr:1937 byr:1937\n",
+            Day04.read_options(),
+        );
+        let count = |mode: Mode, part2: bool, warnings: &mut Vec<Warning>| {
+            count_valid(input.lines(), &Schema::default(), mode, part2, warnings)
+        };
+        // both parts fail in strict mode, on the first problem
+        for &part2 in [false, true].iter() {
+            let err = count(Mode::Strict, part2, &mut Vec::new()).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 2, column 1: parse error: unknown key: xyz"
+            );
+        }
+        // and both count the first record in lenient mode
+        let mut warnings = Vec::new();
+        assert_eq!(count(Mode::Lenient, false, &mut warnings).unwrap(), 1);
+        assert_eq!(count(Mode::Lenient, true, &mut Vec::new()).unwrap(), 1);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(Day04.warnings(&Day04.parse(&input).unwrap()), warnings);
+        assert_eq!(
+            warnings[1].to_string(),
+            "line 4, column 10: warning: duplicate key: byr - first at line 4, column 1"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Passports with typed values, normalised so that other tools can compare
+//! them: heights in centimetres, colours as RGB.
+
+use crate::record::Record;
+use crate::schema::{FieldType, Schema, HEIGHT_UNITS};
+
+/// The value of a field, typed after the field type of the schema.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Year(u16),
+    /// A height in centimetres, rounded to two decimals.
+    Height(f64),
+    Colour([u8; 3]),
+    /// The value of an enum, digits (kept as text for the leading zeros) or
+    /// any field.
+    Text(String),
+    /// A value breaking the rule of its field, as is.
+    Invalid(String),
+}
+
+impl Value {
+    /// The value of a field of the type.
+    pub fn parse(kind: &FieldType, value: &str) -> Value {
+        let typed = if !kind.accepts(value) {
+            None
+        } else {
+            match kind {
+                FieldType::Year { .. } => value.parse().ok().map(Value::Year),
+                FieldType::Height { units } => units.iter().find_map(|(unit, _, _)| {
+                    let n: u16 = value.strip_suffix(unit.as_str())?.parse().ok()?;
+                    let (_, cm) = HEIGHT_UNITS.iter().find(|(name, _)| name == unit)?;
+                    Some(Value::Height((f64::from(n) * cm * 100.0).round() / 100.0))
+                }),
+                FieldType::HexColour => u32::from_str_radix(&value[1..], 16)
+                    .ok()
+                    .map(|rgb| Value::Colour([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8])),
+                FieldType::Enum(_) | FieldType::Digits(_) | FieldType::Any => {
+                    Some(Value::Text(value.to_string()))
+                }
+            }
+        };
+        typed.unwrap_or_else(|| Value::Invalid(value.to_string()))
+    }
+}
+
+/// A passport record with its values typed after the schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Passport {
+    /// Line number of the first line of the record.
+    pub line: usize,
+    /// The values indexed like the schema fields, `None` for the missing
+    /// ones.
+    pub values: Vec<Option<Value>>,
+    /// Whether part two counts the passport.
+    pub valid: bool,
+}
+
+impl Passport {
+    /// The passport of a record returned by [`crate::record::Mode::apply`].
+    pub fn from_record(record: &Record, schema: &Schema) -> Passport {
+        let mut values = vec![None; schema.fields().len()];
+        let mut valid = true;
+        for (i, field, _, value) in record.schema_fields(schema) {
+            let value = Value::parse(&field.kind, value);
+            valid &= !matches!(value, Value::Invalid(_));
+            values[i] = Some(value);
+        }
+        let seen: Vec<bool> = values.iter().map(Option::is_some).collect();
+        Passport {
+            line: record.line,
+            valid: valid && schema.is_complete(&seen),
+            values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{records, Mode};
+    use crate::Day04;
+    use utils::{Input, Solution};
+
+    fn passports(text: &str) -> Vec<Passport> {
+        let input = Input::from_text(text, Day04.read_options());
+        let schema = Schema::default();
+        records(input.lines())
+            .map(|r| Mode::Lenient.apply(r, &schema, &mut Vec::new()).unwrap())
+            .map(|r| Passport::from_record(&r, &schema))
+            .collect()
+    }
+
+    #[test]
+    fn test_values() {
+        let schema = Schema::default();
+        let value =
+            |name: &str, value: &str| Value::parse(&schema.field(name).unwrap().1.kind, value);
+        assert_eq!(value("byr", "1937"), Value::Year(1937));
+        assert_eq!(value("hgt", "183cm"), Value::Height(183.0));
+        assert_eq!(value("hgt", "66in"), Value::Height(167.64));
+        assert_eq!(value("hcl", "#623a2f"), Value::Colour([0x62, 0x3a, 0x2f]));
+        assert_eq!(
+            value("pid", "087499704"),
+            Value::Text("087499704".to_string())
+        );
+        assert_eq!(value("hgt", "66"), Value::Invalid("66".to_string()));
+        assert_eq!(
+            value("hcl", "#623A2F"),
+            Value::Invalid("#623A2F".to_string())
+        );
+        let kind = FieldType::Height {
+            units: vec![("m".to_string(), 1, 3), ("ft".to_string(), 3, 9)],
+        };
+        assert_eq!(Value::parse(&kind, "2m"), Value::Height(200.0));
+        assert_eq!(Value::parse(&kind, "6ft"), Value::Height(182.88));
+    }
+
+    #[test]
+    fn test_passports() {
+        let p = passports(include_str!("../testdata/small.txt"));
+        assert_eq!(p.len(), 4);
+        assert_eq!(
+            p.iter().map(|p| p.valid).collect::<Vec<_>>(),
+            vec![true, false, true, false]
+        );
+        assert_eq!(p[0].values[0], Some(Value::Year(1937)));
+        assert_eq!(p[1].values[3], None);
+        assert_eq!(p[2].line, 7);
+        let p = passports("byr:1937 byr:1950 xyz:1\n");
+        assert_eq!(p[0].values[0], Some(Value::Year(1937)));
+        assert!(!p[0].valid);
+        // part two counts the valid ones
+        let input = Input::from_text(include_str!("../testdata/input.txt"), Day04.read_options());
+        let batch = Day04.parse(&input).unwrap();
+        let valid = passports(include_str!("../testdata/input.txt"))
+            .iter()
+            .filter(|p| p.valid)
+            .count();
+        assert_eq!(valid as u32, Day04.part_two(&batch).unwrap());
+    }
+}
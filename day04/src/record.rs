@@ -0,0 +1,238 @@
+//! The passport records of a batch: `key:value` tokens separated by spaces,
+//! spread over one or more lines, a record ending at an empty line.
+
+use crate::schema::{FieldDef, Schema};
+use std::borrow::Borrow;
+use utils::input::tokens;
+use utils::{Error, Line, Result, Warning};
+
+/// A token of a record, with where it starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub line: usize,
+    pub column: usize,
+    /// The text before the first `:`, or the whole token if it has none.
+    pub key: String,
+    /// The text after the first `:`, `None` if the token has none.
+    pub value: Option<String>,
+}
+
+impl Field {
+    /// The column of the value, just after the `:`.
+    pub fn value_column(&self) -> usize {
+        self.column + self.key.len() + 1
+    }
+}
+
+/// A passport record: its fields in the order of the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// Line number of the first line of the record.
+    pub line: usize,
+    pub fields: Vec<Field>,
+}
+
+impl Record {
+    /// Parses the fields of the line into the record.
+    pub fn push_line(&mut self, line: &Line) {
+        for (col, token) in tokens(&line.text) {
+            let (key, value) = match token.split_once(':') {
+                Some((key, value)) => (key, Some(value.to_string())),
+                None => (token, None),
+            };
+            self.fields.push(Field {
+                line: line.number,
+                column: col,
+                key: key.to_string(),
+                value,
+            });
+        }
+    }
+
+    /// The first field of the key.
+    pub fn get(&self, key: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.key == key)
+    }
+
+    /// The fields of a record returned by [`Mode::apply`], each with its
+    /// index and definition in the schema, and its value.
+    pub fn schema_fields<'a>(
+        &'a self,
+        schema: &'a Schema,
+    ) -> impl Iterator<Item = (usize, &'a FieldDef, &'a Field, &'a str)> + 'a {
+        self.fields.iter().filter_map(move |f| {
+            let found = schema.field(&f.key).zip(f.value.as_deref());
+            debug_assert!(found.is_some(), "{} is left out by Mode::apply", f.key);
+            found.map(|((i, def), value)| (i, def, f, value))
+        })
+    }
+}
+
+/// How a batch treats the tokens of a record that are not fields of the
+/// schema: malformed tokens (no `:` or nothing after it), unknown keys and
+/// repeated keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Mode {
+    /// They are errors, with their location.
+    Strict,
+    /// They are warnings, and the tokens are left out of the record.
+    #[default]
+    Lenient,
+}
+
+impl Mode {
+    /// The record with only the well-formed fields of the schema, the first
+    /// of each key. The other tokens are an error in strict mode; in lenient
+    /// mode they are added to the warnings.
+    pub fn apply(
+        self,
+        record: Record,
+        schema: &Schema,
+        warnings: &mut Vec<Warning>,
+    ) -> Result<Record> {
+        let mut fields: Vec<Field> = Vec::with_capacity(record.fields.len());
+        for f in record.fields {
+            let problem = match &f.value {
+                None => Some(format!("malformed field {} - expect <key>:<value>", f.key)),
+                Some(value) if value.is_empty() => {
+                    Some(format!("malformed field {}: - short key-value pair", f.key))
+                }
+                Some(_) if schema.field(&f.key).is_none() => {
+                    Some(format!("unknown key: {}", f.key))
+                }
+                Some(_) => fields.iter().find(|first| first.key == f.key).map(|first| {
+                    format!(
+                        "duplicate key: {} - first at line {}, column {}",
+                        f.key, first.line, first.column
+                    )
+                }),
+            };
+            match (problem, self) {
+                (None, _) => fields.push(f),
+                (Some(msg), Mode::Strict) => {
+                    return Err(Error::parse(msg).at_line(f.line).at_column(f.column))
+                }
+                (Some(msg), Mode::Lenient) => {
+                    warnings.push(Warning::new(msg).at_line(f.line).at_column(f.column))
+                }
+            }
+        }
+        Ok(Record {
+            line: record.line,
+            fields,
+        })
+    }
+}
+
+/// The records of the lines, see [`records`].
+pub struct Records<I> {
+    lines: I,
+}
+
+impl<I> Iterator for Records<I>
+where
+    I: Iterator,
+    I::Item: Borrow<Line>,
+{
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Record> {
+        let mut record: Option<Record> = None;
+        for line in &mut self.lines {
+            let line = line.borrow();
+            if line.text.trim().is_empty() {
+                if record.is_some() {
+                    break;
+                }
+                continue;
+            }
+            record
+                .get_or_insert_with(|| Record {
+                    line: line.number,
+                    fields: Vec::new(),
+                })
+                .push_line(line);
+        }
+        record
+    }
+}
+
+/// The records of the lines, lazily; any number of empty lines separate
+/// them, and lines of blanks count as empty.
+pub fn records<I>(lines: I) -> Records<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Line>,
+{
+    Records {
+        lines: lines.into_iter(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use utils::{Input, Solution};
+
+    #[test]
+    fn test_records() {
+        let input = Input::from_text(
+            "\necl:gry pid:860033327\n  byr:1937\n\n \n\nhcl:#cfa07d cid\nhgt:",
+            Day04.read_options(),
+        );
+        let all: Vec<Record> = records(input.lines()).collect();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].line, 2);
+        assert_eq!(all[0].fields.len(), 3);
+        assert_eq!(
+            all[0].get("byr"),
+            Some(&Field {
+                line: 3,
+                column: 3,
+                key: "byr".to_string(),
+                value: Some("1937".to_string()),
+            })
+        );
+        assert_eq!(all[0].get("byr").unwrap().value_column(), 7);
+        assert_eq!(all[1].line, 7);
+        assert_eq!(all[1].fields[1].value, None);
+        assert_eq!(all[1].fields[2].value.as_deref(), Some(""));
+        // owned lines work as well
+        assert_eq!(records(input.lines().to_vec()).count(), 2);
+    }
+
+    #[test]
+    fn test_modes() {
+        let input = Input::from_text(
+            "ecl:gry pid:860033327 xyz:1
+byr byr:1937 ecl:brn iyr:
+",
+            Day04.read_options(),
+        );
+        let record = records(input.lines()).next().unwrap();
+        let schema = Schema::default();
+        let mut warnings = Vec::new();
+        let err = Mode::Strict
+            .apply(record.clone(), &schema, &mut warnings)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 23: parse error: unknown key: xyz"
+        );
+        assert!(warnings.is_empty());
+        let clean = Mode::Lenient.apply(record, &schema, &mut warnings).unwrap();
+        let keys: Vec<&str> = clean.fields.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, vec!["ecl", "pid", "byr"]);
+        let warnings: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "line 1, column 23: warning: unknown key: xyz",
+                "line 2, column 1: warning: malformed field byr - expect <key>:<value>",
+                "line 2, column 14: warning: duplicate key: ecl - first at line 1, column 1",
+                "line 2, column 22: warning: malformed field iyr: - short key-value pair",
+            ]
+        );
+    }
+}
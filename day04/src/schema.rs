@@ -0,0 +1,326 @@
+//! The passport fields and the rules their values must follow, read from a
+//! schema file; `day04/schema.txt` holds the rules of the puzzle and is the
+//! default.
+
+use std::fmt;
+use utils::input::tokens;
+use utils::{Error, Input, ReadOptions, Result, Source};
+
+/// The schema of the puzzle.
+pub const DEFAULT_SCHEMA: &str = include_str!("../schema.txt");
+
+/// The units a height may have, with their length in centimetres.
+pub const HEIGHT_UNITS: [(&str, f64); 5] = [
+    ("mm", 0.1),
+    ("cm", 1.0),
+    ("m", 100.0),
+    ("in", 2.54),
+    ("ft", 30.48),
+];
+
+/// The type of a field, checked against its value in part two.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldType {
+    /// A year within the range, both ends included.
+    Year {
+        min: u16,
+        max: u16,
+    },
+    /// A number followed by one of the units, within the range of the unit;
+    /// the units are those of [`HEIGHT_UNITS`].
+    Height {
+        units: Vec<(String, u16, u16)>,
+    },
+    /// `#` followed by six digits `0-9a-f`.
+    HexColour,
+    /// One of the values.
+    Enum(Vec<String>),
+    /// Exactly that many digits `0-9`.
+    Digits(usize),
+    Any,
+}
+
+impl FieldType {
+    /// Whether the value follows the rule of the type.
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            FieldType::Year { min, max } => value
+                .parse::<u16>()
+                .is_ok_and(|year| (*min..=*max).contains(&year)),
+            FieldType::Height { units } => units.iter().any(|(unit, min, max)| {
+                value
+                    .strip_suffix(unit.as_str())
+                    .and_then(|n| n.parse::<u16>().ok())
+                    .is_some_and(|n| (*min..=*max).contains(&n))
+            }),
+            FieldType::HexColour => {
+                value.len() == 7
+                    && value.starts_with('#')
+                    && value[1..]
+                        .bytes()
+                        .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+            }
+            FieldType::Enum(values) => values.iter().any(|v| v == value),
+            FieldType::Digits(n) => value.len() == *n && value.bytes().all(|b| b.is_ascii_digit()),
+            FieldType::Any => true,
+        }
+    }
+}
+
+/// The rule of the type, e.g. `a year in 1920-2002`.
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Year { min, max } => write!(f, "a year in {}-{}", min, max),
+            FieldType::Height { units } => {
+                let units: Vec<String> = units
+                    .iter()
+                    .map(|(unit, min, max)| format!("{}-{}{}", min, max, unit))
+                    .collect();
+                write!(f, "a height in {}", units.join(" or "))
+            }
+            FieldType::HexColour => write!(f, "a colour #rrggbb in lowercase hex"),
+            FieldType::Enum(values) => write!(f, "one of {}", values.join(", ")),
+            FieldType::Digits(n) => write!(f, "{} digits", n),
+            FieldType::Any => write!(f, "anything"),
+        }
+    }
+}
+
+/// A field of the schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    pub required: bool,
+    pub kind: FieldType,
+}
+
+impl FieldDef {
+    /// Checks the value, the error naming the field and the rule it broke.
+    pub fn check(&self, value: &str) -> Result<()> {
+        if self.kind.accepts(value) {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "invalid input - {}:{} is not {}",
+                self.name, value, self.kind
+            )))
+        }
+    }
+}
+
+/// The fields a passport may have.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    fields: Vec<FieldDef>,
+}
+
+impl Schema {
+    /// Parses a schema, one field per line:
+    /// `<name> required|optional <type> [<arguments>]`; `#` starts a
+    /// comment. See `day04/schema.txt` for the types.
+    pub fn parse(input: &Input) -> Result<Schema> {
+        let mut fields: Vec<FieldDef> = Vec::new();
+        for line in input.lines() {
+            let text = line.text.split('#').next().unwrap_or("");
+            let words: Vec<(usize, &str)> = tokens(text).collect();
+            if words.is_empty() {
+                continue;
+            }
+            let field = parse_field(&words).map_err(|e| e.at_line(line.number))?;
+            if fields.iter().any(|f| f.name == field.name) {
+                return Err(Error::parse(format!("duplicate field {}", field.name))
+                    .at_line(line.number)
+                    .at_column(words[0].0));
+            }
+            fields.push(field);
+        }
+        if fields.is_empty() {
+            return Err(Error::validation("the schema has no fields"));
+        }
+        Ok(Schema { fields })
+    }
+
+    /// Reads a schema file.
+    pub fn read(source: &Source) -> Result<Schema> {
+        let input = Input::read(source, ReadOptions::default())?;
+        Schema::parse(&input).map_err(|e| e.in_file(input.name()))
+    }
+
+    pub fn fields(&self) -> &[FieldDef] {
+        &self.fields
+    }
+
+    /// The field of the name and its index in [`Schema::fields`].
+    pub fn field(&self, name: &str) -> Option<(usize, &FieldDef)> {
+        self.fields.iter().enumerate().find(|(_, f)| f.name == name)
+    }
+
+    /// Whether all the required fields are marked as seen, `seen` being
+    /// indexed like [`Schema::fields`].
+    pub fn is_complete(&self, seen: &[bool]) -> bool {
+        self.fields
+            .iter()
+            .zip(seen.iter())
+            .all(|(field, &seen)| seen || !field.required)
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Schema {
+        Schema::parse(&Input::from_text(DEFAULT_SCHEMA, ReadOptions::default()))
+            .expect("the default schema is valid")
+    }
+}
+
+// parse_field parses the words of a schema line; errors carry the column
+fn parse_field(words: &[(usize, &str)]) -> Result<FieldDef> {
+    let word = |i: usize| words.get(i).copied();
+    let end = words.last().map_or(1, |(col, w)| col + w.len());
+    let missing = |what: &str| Error::parse(format!("expect {}", what)).at_column(end);
+    let number = |(col, w): (usize, &str)| -> Result<u16> {
+        w.parse::<u16>().map_err(|e| Error::from(e).at_column(col))
+    };
+    let (_, name) = word(0).ok_or_else(|| missing("a field name"))?;
+    let required = match word(1) {
+        Some((_, "required")) => true,
+        Some((_, "optional")) => false,
+        Some((col, w)) => {
+            return Err(
+                Error::parse(format!("expect required or optional, found {}", w)).at_column(col),
+            )
+        }
+        None => return Err(missing("required or optional")),
+    };
+    let (col, kind) = word(2).ok_or_else(|| missing("a field type"))?;
+    let args = &words[3..];
+    let kind = match kind {
+        "year" => match args {
+            [min, max] => FieldType::Year {
+                min: number(*min)?,
+                max: number(*max)?,
+            },
+            _ => return Err(Error::parse("expect year <min> <max>").at_column(col)),
+        },
+        "height" if !args.is_empty() && args.len().is_multiple_of(3) => {
+            let mut units = Vec::new();
+            for unit in args.chunks(3) {
+                if !HEIGHT_UNITS.iter().any(|(name, _)| *name == unit[0].1) {
+                    return Err(Error::parse(format!("unknown height unit {}", unit[0].1))
+                        .at_column(unit[0].0));
+                }
+                units.push((unit[0].1.to_string(), number(unit[1])?, number(unit[2])?));
+            }
+            FieldType::Height { units }
+        }
+        "height" => return Err(Error::parse("expect height <unit> <min> <max>...").at_column(col)),
+        "hex-colour" if args.is_empty() => FieldType::HexColour,
+        "enum" if !args.is_empty() => {
+            FieldType::Enum(args.iter().map(|(_, w)| w.to_string()).collect())
+        }
+        "enum" => return Err(Error::parse("expect enum <value>...").at_column(col)),
+        "digits" => match args {
+            [n] => FieldType::Digits(number(*n)? as usize),
+            _ => return Err(Error::parse("expect digits <count>").at_column(col)),
+        },
+        "any" if args.is_empty() => FieldType::Any,
+        "hex-colour" | "any" => {
+            return Err(Error::parse(format!("{} takes no arguments", kind)).at_column(args[0].0))
+        }
+        _ => return Err(Error::parse(format!("unknown field type {}", kind)).at_column(col)),
+    };
+    Ok(FieldDef {
+        name: name.to_string(),
+        required,
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Schema> {
+        Schema::parse(&Input::from_text(text, ReadOptions::default()))
+    }
+
+    #[test]
+    fn test_default() {
+        let schema = Schema::default();
+        assert_eq!(schema.fields().len(), 8);
+        let (i, hgt) = schema.field("hgt").unwrap();
+        assert_eq!(i, 3);
+        assert_eq!(hgt.kind.to_string(), "a height in 150-193cm or 59-76in");
+        assert!(!schema.field("cid").unwrap().1.required);
+        let mut seen = vec![true; 8];
+        seen[7] = false;
+        assert!(schema.is_complete(&seen));
+        seen[0] = false;
+        assert!(!schema.is_complete(&seen));
+    }
+
+    #[test]
+    fn test_types() {
+        let schema = Schema::default();
+        let accepts = |name: &str, value: &str| schema.field(name).unwrap().1.kind.accepts(value);
+        assert!(accepts("byr", "2002") && !accepts("byr", "2003") && !accepts("byr", "x"));
+        assert!(accepts("hgt", "60in") && accepts("hgt", "190cm"));
+        assert!(!accepts("hgt", "190in") && !accepts("hgt", "190") && !accepts("hgt", "cm"));
+        assert!(accepts("hcl", "#123abc") && !accepts("hcl", "#123abz"));
+        assert!(!accepts("hcl", "123abc") && !accepts("hcl", "#123ABC"));
+        assert!(accepts("ecl", "brn") && !accepts("ecl", "wat"));
+        assert!(accepts("pid", "000000001") && !accepts("pid", "0123456789"));
+        assert!(accepts("cid", ""));
+        let err = schema.field("byr").unwrap().1.check("2003").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "validation error: invalid input - byr:2003 is not a year in 1920-2002"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let schema =
+            parse("# ids\nid required digits 4 # four\nsize optional height m 1 3 ft 3 9\n")
+                .unwrap();
+        assert_eq!(schema.fields().len(), 2);
+        assert!(schema.fields()[1].kind.accepts("8ft"));
+        let error = |text: &str| parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("\n# none\n"),
+            "validation error: the schema has no fields"
+        );
+        assert_eq!(
+            error("id needed any"),
+            "line 1, column 4: parse error: expect required or optional, found needed"
+        );
+        assert_eq!(
+            error("id required"),
+            "line 1, column 12: parse error: expect a field type"
+        );
+        assert_eq!(
+            error("a optional any\nid required year 1 x"),
+            "line 2, column 20: parse error: invalid number: invalid digit found in string"
+        );
+        assert_eq!(
+            error("id required height cm 1"),
+            "line 1, column 13: parse error: expect height <unit> <min> <max>..."
+        );
+        assert_eq!(
+            error("id required height cm 1 2 yd 1 2"),
+            "line 1, column 27: parse error: unknown height unit yd"
+        );
+        assert_eq!(
+            error("id required any 3"),
+            "line 1, column 17: parse error: any takes no arguments"
+        );
+        assert_eq!(
+            error("id required uuid"),
+            "line 1, column 13: parse error: unknown field type uuid"
+        );
+        assert_eq!(
+            error("id required any\nid optional any"),
+            "line 2, column 1: parse error: duplicate field id"
+        );
+    }
+}
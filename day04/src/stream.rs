@@ -0,0 +1,444 @@
+//! Stream mode: validates batches of any size with bounded memory. The
+//! records are read lazily and checked in chunks by a pool of threads; the
+//! outcome of each chunk is passed on in the order of the input.
+//!
+//! `day04 stream [--schema PATH] [--strict|--lenient] [--threads N] [--chunk N]
+//! [--list] [--all] [--progress] [inputfile|-]`
+
+use crate::diagnose::{diagnose_record, to_text, Diagnosis};
+use crate::record::{records, Mode};
+use crate::schema::Schema;
+use crate::Day04;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+use std::iter::Peekable;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use utils::{cli, Error, Line, Result, Solution, Source, Warning};
+
+/// How a stream is split between the threads.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub threads: usize,
+    /// Records per chunk, the unit of work of a thread. At most two chunks
+    /// per thread are held in memory at any time.
+    pub chunk: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            mode: Mode::default(),
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            chunk: 1000,
+        }
+    }
+}
+
+/// What a stream has gone through so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Totals {
+    pub records: u64,
+    pub lines: u64,
+    /// The bytes of the lines, end of line included.
+    pub bytes: u64,
+    /// The records with all the required fields, counted by part one.
+    pub complete: u64,
+    /// The records counted by part two.
+    pub valid: u64,
+}
+
+/// The totals and how fast they were reached, e.g.
+/// `2000 records, 9000 lines, 0.4 MB in 0.1s: 20000 records/s, 4.0 MB/s`.
+pub struct Throughput {
+    pub totals: Totals,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.elapsed.as_secs_f64().max(1e-9);
+        let mb = self.totals.bytes as f64 / 1e6;
+        write!(
+            f,
+            "{} records, {} lines, {:.1} MB in {:.1}s: {:.0} records/s, {:.1} MB/s",
+            self.totals.records,
+            self.totals.lines,
+            mb,
+            self.elapsed.as_secs_f64(),
+            self.totals.records as f64 / secs,
+            mb / secs
+        )
+    }
+}
+
+// the lines of a chunk of records, the empty lines between them included
+struct Chunk {
+    seq: usize,
+    lines: Vec<Line>,
+}
+
+// a chunk of records checked
+struct Checked {
+    diagnoses: Vec<Diagnosis>,
+    warnings: Vec<Warning>,
+    lines: u64,
+    bytes: u64,
+}
+
+// takes the lines of the next `n` records, stopping before the first line
+// of the one after
+fn next_chunk<I>(lines: &mut Peekable<I>, n: usize) -> Vec<Line>
+where
+    I: Iterator<Item = Line>,
+{
+    let mut chunk = Vec::new();
+    let (mut records, mut in_record) = (0, false);
+    while let Some(line) = lines.peek() {
+        let empty = line.text.trim().is_empty();
+        if !empty && !in_record {
+            if records == n {
+                break;
+            }
+            records += 1;
+        }
+        in_record = !empty;
+        chunk.extend(lines.next());
+    }
+    chunk
+}
+
+// the records are parsed here, on the worker threads
+fn check_chunk(lines: Vec<Line>, schema: &Schema, mode: Mode) -> Result<Checked> {
+    let bytes = lines.iter().map(|line| line.text.len() as u64 + 1).sum();
+    let mut checked = Checked {
+        diagnoses: Vec::new(),
+        warnings: Vec::new(),
+        lines: lines.len() as u64,
+        bytes,
+    };
+    for record in records(lines) {
+        let record = mode.apply(record, schema, &mut checked.warnings)?;
+        checked.diagnoses.push(diagnose_record(&record, schema));
+    }
+    Ok(checked)
+}
+
+/// Validates the records of the lines, chunk by chunk across the threads of
+/// the options, and calls `sink` with the diagnoses and warnings of every
+/// chunk, in the order of the input, and the totals so far. The first error,
+/// from the lines, the mode or the sink, stops the stream.
+pub fn validate_stream<I, F>(
+    lines: I,
+    schema: &Schema,
+    options: Options,
+    mut sink: F,
+) -> Result<Totals>
+where
+    I: Iterator<Item = Result<Line>>,
+    F: FnMut(&[Diagnosis], &[Warning], &Totals) -> Result<()>,
+{
+    let threads = options.threads.max(1);
+    let window = 2 * threads;
+    let mut read_error = None;
+    let mut lines = lines
+        .map_while(|line| line.map_err(|e| read_error = Some(e)).ok())
+        .peekable();
+    let mut totals = Totals::default();
+    thread::scope(|scope| {
+        let (work_tx, work_rx) = mpsc::channel::<Chunk>();
+        let (done_tx, done_rx) = mpsc::channel::<(usize, Result<Checked>)>();
+        let work_rx = Arc::new(Mutex::new(work_rx));
+        for _ in 0..threads {
+            let (work_rx, done_tx) = (Arc::clone(&work_rx), done_tx.clone());
+            scope.spawn(move || loop {
+                let chunk = match work_rx.lock() {
+                    Ok(rx) => rx.recv(),
+                    Err(_) => return,
+                };
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(_) => return,
+                };
+                let checked = check_chunk(chunk.lines, schema, options.mode);
+                if done_tx.send((chunk.seq, checked)).is_err() {
+                    return;
+                }
+            });
+        }
+        drop(done_tx);
+        // chunks come back in any order and wait here for their turn; the
+        // chunks sent and not yet passed on never exceed the window
+        let mut pending: BTreeMap<usize, Result<Checked>> = BTreeMap::new();
+        let (mut sent, mut passed) = (0, 0);
+        let mut eof = false;
+        while !eof || passed < sent {
+            if !eof && sent - passed < window {
+                let chunk = next_chunk(&mut lines, options.chunk.max(1));
+                if chunk.is_empty() {
+                    eof = true;
+                    continue;
+                }
+                work_tx
+                    .send(Chunk {
+                        seq: sent,
+                        lines: chunk,
+                    })
+                    .map_err(|_| Error::validation("worker thread died"))?;
+                sent += 1;
+                continue;
+            }
+            let (seq, checked) = done_rx
+                .recv()
+                .map_err(|_| Error::validation("worker thread died"))?;
+            pending.insert(seq, checked);
+            while let Some(checked) = pending.remove(&passed) {
+                let checked = checked?;
+                totals.records += checked.diagnoses.len() as u64;
+                totals.lines += checked.lines;
+                totals.bytes += checked.bytes;
+                for d in checked.diagnoses.iter() {
+                    totals.complete += d.missing.is_empty() as u64;
+                    totals.valid += d.is_valid() as u64;
+                }
+                sink(&checked.diagnoses, &checked.warnings, &totals)?;
+                passed += 1;
+            }
+        }
+        Ok::<(), Error>(())
+    })?;
+    drop(lines); // it holds on to read_error
+    match read_error {
+        Some(err) => Err(err),
+        None => Ok(totals),
+    }
+}
+
+struct Args {
+    schema: Option<Source>,
+    options: Options,
+    list: bool,
+    all: bool,
+    progress: bool,
+    source: Source,
+}
+
+const USAGE: &str =
+    "usage: day04 stream [--schema PATH] [--strict|--lenient] [--threads N] [--chunk N]
+                    [--list] [--all] [--progress] [inputfile|-]
+validates a batch of any size with bounded memory, in chunks of records
+(1000 by default) checked on several threads (one per CPU by default), and
+prints the answers of both parts; --list also prints the invalid records as
+diagnose does, in the order of the input, --all the valid ones too;
+--progress reports the throughput every second, on stderr like the total";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut schema = None;
+    let mut options = Options::default();
+    let mut list = false;
+    let mut all = false;
+    let mut progress = false;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--schema" => schema = Some(Source::from_arg(cli::option_value(arg, &mut it)?)),
+            "--strict" => options.mode = Mode::Strict,
+            "--lenient" => options.mode = Mode::Lenient,
+            "--threads" => {
+                options.threads = cli::positive_arg(arg, cli::option_value(arg, &mut it)?)?
+            }
+            "--chunk" => options.chunk = cli::positive_arg(arg, cli::option_value(arg, &mut it)?)?,
+            "--list" => list = true,
+            "--all" => all = true,
+            "--progress" => progress = true,
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        schema,
+        options,
+        list,
+        all,
+        progress,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_stream(args: &Args, out: &mut dyn Write) -> Result<()> {
+    let schema = match &args.schema {
+        Some(source) => Schema::read(source)?,
+        None => Schema::default(),
+    };
+    let name = args.source.name();
+    let lines = args.source.lines(Day04.read_options())?;
+    let start = Instant::now();
+    let mut reported = start;
+    let totals = validate_stream(
+        lines,
+        &schema,
+        args.options,
+        |diagnoses, warnings, totals| {
+            for warning in warnings {
+                eprintln!("{}", warning.clone().in_file(&name));
+            }
+            if args.list {
+                let listed: Vec<Diagnosis> = diagnoses
+                    .iter()
+                    .filter(|d| args.all || !d.is_valid())
+                    .cloned()
+                    .collect();
+                out.write_all(to_text(&listed).as_bytes())?;
+            }
+            if args.progress && reported.elapsed() >= Duration::from_secs(1) {
+                reported = Instant::now();
+                let throughput = Throughput {
+                    totals: *totals,
+                    elapsed: start.elapsed(),
+                };
+                eprintln!("{}", throughput);
+            }
+            Ok(())
+        },
+    )
+    .map_err(|e| e.in_file(&name))?;
+    writeln!(
+        out,
+        "part one: {}\npart two: {}",
+        totals.complete, totals.valid
+    )?;
+    let throughput = Throughput {
+        totals,
+        elapsed: start.elapsed(),
+    };
+    eprintln!("{}", throughput);
+    Ok(())
+}
+
+/// Entry point of `day04 stream`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::stream_subcommand(args, parse_args, run_stream, USAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnose::diagnose;
+    use utils::Input;
+
+    fn lines(text: &str) -> impl Iterator<Item = Result<Line>> {
+        Source::Text(text.to_string())
+            .lines(Day04.read_options())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stream() {
+        let text = include_str!("../testdata/input.txt");
+        let input = Input::from_text(text, Day04.read_options());
+        let expected = diagnose(
+            input.lines(),
+            &Schema::default(),
+            Mode::Strict,
+            &mut Vec::new(),
+        )
+        .unwrap();
+        for &(threads, chunk) in [(1, 1000), (4, 1), (3, 7), (8, 50)].iter() {
+            let options = Options {
+                mode: Mode::Strict,
+                threads,
+                chunk,
+            };
+            let mut seen: Vec<Diagnosis> = Vec::new();
+            let mut chunks = 0;
+            let totals = validate_stream(lines(text), &Schema::default(), options, |d, _, t| {
+                seen.extend_from_slice(d);
+                chunks += 1;
+                assert_eq!(t.records, seen.len() as u64);
+                Ok(())
+            })
+            .unwrap();
+            // in the order of the input, whatever the threads
+            assert_eq!(seen, expected);
+            assert_eq!(chunks, expected.len().div_ceil(chunk));
+            assert_eq!((totals.complete, totals.valid), (247, 145));
+            assert_eq!(totals.records, expected.len() as u64);
+            assert_eq!(totals.lines, input.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_stream_modes() {
+        let text = "byr:1937\n\nbyr:1937 byr:1940\n\nfoo:1\n\npid:1\n";
+        let options = Options {
+            mode: Mode::Strict,
+            threads: 2,
+            chunk: 1,
+        };
+        // strict mode stops at the first problem, after the chunks before it
+        let mut records = 0;
+        let err = validate_stream(lines(text), &Schema::default(), options, |d, _, _| {
+            records += d.len();
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 10: parse error: duplicate key: byr - first at line 3, column 1"
+        );
+        assert_eq!(records, 1);
+        let options = Options {
+            mode: Mode::Lenient,
+            ..options
+        };
+        let mut warnings = Vec::new();
+        let totals = validate_stream(lines(text), &Schema::default(), options, |_, w, _| {
+            warnings.extend_from_slice(w);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(totals.records, 4);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1].location.line, Some(5));
+        // so does an error of the sink
+        let err = validate_stream(lines(text), &Schema::default(), options, |_, _, t| {
+            if t.records == 2 {
+                return Err(Error::validation("stop"));
+            }
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "validation error: stop");
+    }
+
+    #[test]
+    fn test_next_chunk() {
+        let text = "\na:1\nb:2\n\n\nc:3\n\nd:4\n";
+        let mut lines = lines(text).map(|line| line.unwrap()).peekable();
+        let numbers = |chunk: Vec<Line>| chunk.iter().map(|l| l.number).collect::<Vec<_>>();
+        assert_eq!(numbers(next_chunk(&mut lines, 1)), vec![1, 2, 3, 4, 5]);
+        assert_eq!(numbers(next_chunk(&mut lines, 2)), vec![6, 7, 8]);
+        assert!(next_chunk(&mut lines, 2).is_empty());
+    }
+
+    #[test]
+    fn test_throughput() {
+        let throughput = Throughput {
+            totals: Totals {
+                records: 2000,
+                lines: 9000,
+                bytes: 400_000,
+                complete: 10,
+                valid: 5,
+            },
+            elapsed: Duration::from_millis(100),
+        };
+        assert_eq!(
+            throughput.to_string(),
+            "2000 records, 9000 lines, 0.4 MB in 0.1s: 20000 records/s, 4.0 MB/s"
+        );
+    }
+}
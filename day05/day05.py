@@ -6,19 +6,29 @@ tbl = "FBLR".maketrans({'F': '0', 'B': '1', 'L': '0', 'R': '1'})
 def pass2id(passport): 
 	return int(passport.translate(tbl), 2)
 
-max = -1
-min = -1
-numseats = 2**11
-seats = [0] * numseats # map of potential 2048 seats
-f = open("testdata/input.txt", "r")
-for line in f:
-	id = pass2id(line.strip())
-	seats[id] = 1
-	if id > max:
-		max = id
-	if min == -1 or id < min:
-		min = id
-print("part 1:", max)
-for i in range(min, max+1):
-	if seats[i] == 0:
-		print("part 2: ", i)
+# the inverse: 7 row letters F/B then 3 column letters L/R
+def id2pass(id):
+	bits = format(id, "010b")
+	return bits[:7].translate({48: 'F', 49: 'B'}) + bits[7:].translate({48: 'L', 49: 'R'})
+
+def test_roundtrip():
+	for id in range(2**10):
+		assert pass2id(id2pass(id)) == id
+
+if __name__ == "__main__":
+	max = -1
+	min = -1
+	numseats = 2**11
+	seats = [0] * numseats # map of potential 2048 seats
+	f = open("testdata/input.txt", "r")
+	for line in f:
+		id = pass2id(line.strip())
+		seats[id] = 1
+		if id > max:
+			max = id
+		if min == -1 or id < min:
+			min = id
+	print("part 1:", max)
+	for i in range(min, max+1):
+		if seats[i] == 0:
+			print("part 2: ", i)
@@ -0,0 +1,115 @@
+//! Encode mode: the boarding passes of seats, the inverse of seats mode, to
+//! generate passes for test fixtures and scanners.
+//!
+//! `day05 encode [LAYOUT OPTIONS] [inputfile|-]`
+//!
+//! Every line of the input is a seat id, or a row and a column as
+//! `ROW,COLUMN`.
+
+use crate::seats::{LayoutArgs, LAYOUT_USAGE};
+use crate::{Day05, SeatLayout};
+use utils::{cli, Error, Input, Line, Result, Solution, Source};
+
+/// The pass of a line: a seat id or `ROW,COLUMN`.
+pub fn encode_line(layout: &SeatLayout, line: &str) -> Result<String> {
+    let number = |text: &str, col: usize| -> Result<u32> {
+        text.trim()
+            .parse::<u32>()
+            .map_err(|e| Error::from(e).at_column(col))
+    };
+    match line.split_once(',') {
+        Some((row, column)) => layout.encode(number(row, 1)?, number(column, row.len() + 2)?),
+        None => layout.encode_id(number(line, 1)?),
+    }
+}
+
+/// The passes of the lines, one per line; errors carry the line.
+pub fn encode_lines<'a, I>(layout: &SeatLayout, lines: I) -> Result<String>
+where
+    I: IntoIterator<Item = &'a Line>,
+{
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&encode_line(layout, &line.text).map_err(|e| e.at_line(line.number))?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+struct Args {
+    layout: SeatLayout,
+    source: Source,
+}
+
+const USAGE: &str = "usage: day05 encode [--rows N] [--columns N] [--row-letters XY]
+                    [--column-letters XY] [inputfile|-]
+prints the boarding pass of every seat id, or ROW,COLUMN, of the input";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut layout = LayoutArgs::default();
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            opt if layout.parse_arg(opt, &mut it)? => (),
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        layout: layout.layout()?,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_encode(args: &Args) -> Result<String> {
+    let input = Input::read(&args.source, Day05.read_options())?;
+    encode_lines(&args.layout, input.lines()).map_err(|e| e.in_file(input.name()))
+}
+
+/// Entry point of `day05 encode`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(
+        args,
+        parse_args,
+        run_encode,
+        &format!("{}\n{}", USAGE, LAYOUT_USAGE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::ReadOptions;
+
+    #[test]
+    fn test_encode_lines() {
+        let layout = SeatLayout::default();
+        let input = Input::from_text("357\n44,5\n 70 , 7\n", ReadOptions::default());
+        assert_eq!(
+            encode_lines(&layout, input.lines()).unwrap(),
+            "FBFBBFFRLR\nFBFBBFFRLR\nBFFFBBFRRR\n"
+        );
+        let input = Input::from_text("357\n44,x\n", ReadOptions::default());
+        assert_eq!(
+            encode_lines(&layout, input.lines())
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 4: parse error: invalid number: invalid digit found in string"
+        );
+        let input = Input::from_text("2000\n", ReadOptions::default());
+        let err = encode_lines(&layout, input.lines()).unwrap_err();
+        assert_eq!(err.location().unwrap().line, Some(1));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // the passes of the puzzle input encode back to themselves
+        let input = Input::from_text(include_str!("../testdata/input.txt"), Day05.read_options());
+        let layout = SeatLayout::default();
+        let seats = layout.decode_all(input.lines()).unwrap();
+        let ids: String = seats.iter().map(|seat| format!("{}\n", seat.id)).collect();
+        let ids = Input::from_text(&ids, ReadOptions::default());
+        let passes: Vec<String> = input.texts().map(|pass| format!("{}\n", pass)).collect();
+        assert_eq!(encode_lines(&layout, ids.lines()).unwrap(), passes.concat());
+    }
+}
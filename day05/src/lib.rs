@@ -0,0 +1,409 @@
+use utils::{Error, Input, Line, ReadOptions, Result, Solution, Trim};
+
+pub mod encode;
+pub mod seats;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed = Vec<Seat>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    // decodes every boarding pass into its seat
+    fn parse(&self, input: &Input) -> Result<Vec<Seat>> {
+        SeatLayout::default().decode_all(input.lines())
+    }
+
+    fn part_one(&self, seats: &Vec<Seat>) -> Result<u32> {
+        match seats.iter().map(|seat| seat.id).max() {
+            Some(max) => Ok(max),
+            None => Err(Error::no_solution("no boarding passes")),
+        }
+    }
+
+    fn part_two(&self, seats: &Vec<Seat>) -> Result<u32> {
+        find_my_seat(seats, &SeatLayout::default())
+    }
+}
+
+/// A seat decoded from a boarding pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u32,
+    pub column: u32,
+    /// The row times the number of columns, plus the column.
+    pub id: u32,
+}
+
+/// The geometry of the boarding passes of an aircraft: so many characters
+/// halving the rows, then so many halving the columns, each one of two
+/// letters for the lower and the upper half. The puzzle has 7 row
+/// characters `F` or `B` and 3 column characters `L` or `R`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeatLayout {
+    rows: usize,
+    columns: usize,
+    row_letters: [char; 2],
+    column_letters: [char; 2],
+}
+
+/// The most characters a pass may have, so that seat ids fit in a `u32`.
+pub const MAX_PASS_LEN: usize = 32;
+
+impl SeatLayout {
+    /// A layout of `rows` row characters and `columns` column characters,
+    /// the letters given as `[lower, upper]`.
+    pub fn new(
+        rows: usize,
+        columns: usize,
+        row_letters: [char; 2],
+        column_letters: [char; 2],
+    ) -> Result<SeatLayout> {
+        if rows == 0 || columns == 0 {
+            return Err(Error::validation(
+                "a layout needs at least one row and one column character",
+            ));
+        }
+        if rows + columns > MAX_PASS_LEN {
+            return Err(Error::validation(format!(
+                "a layout has at most {} characters, not {}",
+                MAX_PASS_LEN,
+                rows + columns
+            )));
+        }
+        for letters in [row_letters, column_letters].iter() {
+            if letters[0] == letters[1] {
+                return Err(Error::validation(format!(
+                    "the letters of the lower and the upper half are both {:?}",
+                    letters[0]
+                )));
+            }
+        }
+        Ok(SeatLayout {
+            rows,
+            columns,
+            row_letters,
+            column_letters,
+        })
+    }
+
+    /// The number of row characters.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of column characters.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn row_letters(&self) -> [char; 2] {
+        self.row_letters
+    }
+
+    pub fn column_letters(&self) -> [char; 2] {
+        self.column_letters
+    }
+
+    /// The number of characters of a pass.
+    pub fn pass_len(&self) -> usize {
+        self.rows + self.columns
+    }
+
+    /// The number of seats, and of seat ids.
+    pub fn seats(&self) -> u64 {
+        1 << self.pass_len()
+    }
+
+    /// The id of the seat at the row and column.
+    pub fn seat_id(&self, row: u32, column: u32) -> u32 {
+        row << self.columns | column
+    }
+
+    /// Decodes a boarding pass; errors carry the column.
+    pub fn decode(&self, pass: &str) -> Result<Seat> {
+        let len = pass.chars().count();
+        if len != self.pass_len() {
+            return Err(Error::parse(format!(
+                "invalid input - pass length {} != {}",
+                len,
+                self.pass_len()
+            ))
+            .at_column(1));
+        }
+        let (mut row, mut column) = (0u32, 0u32);
+        for (i, (col, c)) in pass.char_indices().enumerate() {
+            let (letters, value) = if i < self.rows {
+                (self.row_letters, &mut row)
+            } else {
+                (self.column_letters, &mut column)
+            };
+            let half = letters.iter().position(|&l| l == c).ok_or_else(|| {
+                Error::parse(format!(
+                    "invalid input - unknown character {:?}, expect {} or {}",
+                    c, letters[0], letters[1]
+                ))
+                .at_column(col + 1)
+            })?;
+            *value = *value << 1 | half as u32;
+        }
+        Ok(Seat {
+            row,
+            column,
+            id: self.seat_id(row, column),
+        })
+    }
+
+    /// The boarding pass of the seat at the row and column.
+    pub fn encode(&self, row: u32, column: u32) -> Result<String> {
+        for &(what, value, chars) in
+            [("row", row, self.rows), ("column", column, self.columns)].iter()
+        {
+            if u64::from(value) >= 1 << chars {
+                return Err(Error::validation(format!(
+                    "{} {} is outside the {} {}s of the layout",
+                    what,
+                    value,
+                    1u64 << chars,
+                    what
+                )));
+            }
+        }
+        let mut pass = String::with_capacity(self.pass_len());
+        for i in (0..self.rows).rev() {
+            pass.push(self.row_letters[(row >> i) as usize & 1]);
+        }
+        for i in (0..self.columns).rev() {
+            pass.push(self.column_letters[(column >> i) as usize & 1]);
+        }
+        Ok(pass)
+    }
+
+    /// The boarding pass of the seat id.
+    pub fn encode_id(&self, id: u32) -> Result<String> {
+        if u64::from(id) >= self.seats() {
+            return Err(Error::validation(format!(
+                "seat id {} is outside the {} seats of the layout",
+                id,
+                self.seats()
+            )));
+        }
+        self.encode(id >> self.columns, id & ((1 << self.columns) - 1))
+    }
+
+    /// Decodes a boarding pass per line; errors carry the line.
+    pub fn decode_all<'a, I>(&self, lines: I) -> Result<Vec<Seat>>
+    where
+        I: IntoIterator<Item = &'a Line>,
+    {
+        let mut seats = Vec::new();
+        for line in lines {
+            seats.push(
+                self.decode(&line.text)
+                    .map_err(|e| e.at_line(line.number))?,
+            );
+        }
+        Ok(seats)
+    }
+}
+
+impl Default for SeatLayout {
+    fn default() -> SeatLayout {
+        SeatLayout {
+            rows: 7,
+            columns: 3,
+            row_letters: ['F', 'B'],
+            column_letters: ['L', 'R'],
+        }
+    }
+}
+
+/// The first free seat id after the lowest one taken: in the puzzle, the
+/// only free seat between two taken ones.
+pub fn find_my_seat(seats: &[Seat], layout: &SeatLayout) -> Result<u32> {
+    let min = match seats.iter().map(|seat| seat.id).min() {
+        Some(min) => min,
+        None => return Err(Error::no_solution("no boarding passes")),
+    };
+    // the table starts at the lowest id; the first free seat after it is at
+    // most one past the number of passes, and within the aircraft
+    let num_seats = (seats.len() as u64 + 1).min(layout.seats() - u64::from(min)) as usize;
+    let mut taken = vec![false; num_seats];
+    for seat in seats.iter() {
+        if let Some(t) = taken.get_mut((seat.id - min) as usize) {
+            *t = true;
+        }
+    }
+    find_empty(&taken, 0).map(|i| i + min)
+}
+
+fn find_empty(seats: &[bool], min: u32) -> Result<u32> {
+    for i in (min as usize)..seats.len() {
+        if seats.get(i) == Some(&false) {
+            return Ok(i as u32);
+        }
+    }
+    Err(Error::no_solution("no empty seat found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn pass2id(pass: &str) -> Result<u32> {
+        SeatLayout::default().decode(pass).map(|seat| seat.id)
+    }
+
+    #[test]
+    fn test_pass2id() {
+        assert_eq!(pass2id("FFFFFFFLLL").unwrap(), 0);
+        assert_eq!(pass2id("FFFFFFFLLR").unwrap(), 1);
+        assert_eq!(pass2id("FFFFFFFLRL").unwrap(), 2);
+        assert_eq!(pass2id("BFFFBBFRRR").unwrap(), 567);
+        assert_eq!(pass2id("FFFBBBFRRR").unwrap(), 119);
+        assert_eq!(pass2id("BBFFBBFRLL").unwrap(), 820);
+    }
+
+    #[test]
+    fn test_find_empty() {
+        let seats = vec![false, false, true, true, false, true]; // empty seat: 4
+        assert_eq!(find_empty(&seats, 2).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_seat() {
+        let seat = SeatLayout::default().decode("FBFBBFFRLR").unwrap();
+        assert_eq!(
+            seat,
+            Seat {
+                row: 44,
+                column: 5,
+                id: 357
+            }
+        );
+        let err = SeatLayout::default().decode("FBFBBFFRL").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "column 1: parse error: invalid input - pass length 9 != 10"
+        );
+        let err = SeatLayout::default().decode("FBFBBFFRBR").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "column 9: parse error: invalid input - unknown character 'B', expect L or R"
+        );
+    }
+
+    #[test]
+    fn test_layouts() {
+        // 4 rows of 8 seats, rows given as up/down
+        let small = SeatLayout::new(2, 3, ['U', 'D'], ['L', 'R']).unwrap();
+        assert_eq!((small.pass_len(), small.seats()), (5, 32));
+        let seat = small.decode("DURLR").unwrap();
+        assert_eq!((seat.row, seat.column, seat.id), (2, 5, 21));
+        assert!(small.decode("FBRLR").is_err());
+        // 256 rows of 16 seats
+        let large = SeatLayout::new(8, 4, ['F', 'B'], ['L', 'R']).unwrap();
+        let seat = large.decode("BBBBBBBBLRRR").unwrap();
+        assert_eq!((seat.row, seat.column, seat.id), (255, 7, 255 * 16 + 7));
+        let input = Input::from_text("BBBBBBBBLLLR\nBBBBBBBBLLRR\n", ReadOptions::default());
+        let seats = large.decode_all(input.lines()).unwrap();
+        assert_eq!(find_my_seat(&seats, &large).unwrap(), 4082);
+        let input = Input::from_text("BBBBBBBBLLLR\nLLLLLLLLLLLL\n", ReadOptions::default());
+        let err = large.decode_all(input.lines()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: parse error: invalid input - unknown character 'L', expect F or B"
+        );
+        // the last seat of a full aircraft is not free
+        let full = SeatLayout::new(1, 1, ['F', 'B'], ['L', 'R']).unwrap();
+        let input = Input::from_text("FL\nFR\nBL\nBR\n", ReadOptions::default());
+        let seats = full.decode_all(input.lines()).unwrap();
+        assert!(find_my_seat(&seats, &full).is_err());
+        // the table follows the passes, not the highest id of a wide layout
+        let wide = SeatLayout::new(20, 12, ['F', 'B'], ['L', 'R']).unwrap();
+        let input = Input::from_text(
+            "BBBBBBBBBBBBBBBBBBBBRRRRRRRRRRRR\nBBBBBBBBBBBBBBBBBBBBRRRRRRRRRRRL\n",
+            ReadOptions::default(),
+        );
+        let seats = wide.decode_all(input.lines()).unwrap();
+        assert!(find_my_seat(&seats, &wide).is_err());
+        let input = Input::from_text(
+            "BBBBBBBBBBBBBBBBBBBBRRRRRRRRRRLL\nBBBBBBBBBBBBBBBBBBBBRRRRRRRRRRRL\n",
+            ReadOptions::default(),
+        );
+        let seats = wide.decode_all(input.lines()).unwrap();
+        assert_eq!(find_my_seat(&seats, &wide).unwrap(), u32::MAX - 2);
+        assert!(SeatLayout::new(0, 3, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatLayout::new(20, 13, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatLayout::new(7, 3, ['F', 'F'], ['L', 'R']).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let layout = SeatLayout::default();
+        assert_eq!(layout.encode(44, 5).unwrap(), "FBFBBFFRLR");
+        assert_eq!(layout.encode_id(820).unwrap(), "BBFFBBFRLL");
+        assert_eq!(layout.encode_id(0).unwrap(), "FFFFFFFLLL");
+        assert_eq!(
+            layout.encode(128, 0).unwrap_err().to_string(),
+            "validation error: row 128 is outside the 128 rows of the layout"
+        );
+        assert_eq!(
+            layout.encode(0, 8).unwrap_err().to_string(),
+            "validation error: column 8 is outside the 8 columns of the layout"
+        );
+        assert_eq!(
+            layout.encode_id(1024).unwrap_err().to_string(),
+            "validation error: seat id 1024 is outside the 1024 seats of the layout"
+        );
+        let wide = SeatLayout::new(1, 31, ['a', 'b'], ['0', '1']).unwrap();
+        let pass = wide.encode_id(u32::MAX).unwrap();
+        assert_eq!(pass, format!("b{}", "1".repeat(31)));
+        assert_eq!(wide.decode(&pass).unwrap().id, u32::MAX);
+    }
+
+    // layouts of 1 to 32 characters, with letters of the layout or other ones
+    fn layouts() -> impl Strategy<Value = SeatLayout> {
+        let letters = prop::sample::select(vec!['F', 'B', 'L', 'R', 'U', 'D', '0', '1']);
+        (
+            1..=16usize,
+            1..=16usize,
+            letters.clone(),
+            letters.clone(),
+            letters.clone(),
+            letters,
+        )
+            .prop_filter("the letters of a half differ", |&(_, _, a, b, c, d)| {
+                a != b && c != d
+            })
+            .prop_map(|(rows, columns, a, b, c, d)| {
+                SeatLayout::new(rows, columns, [a, b], [c, d]).unwrap()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(layout in layouts(), seed: u64) {
+            let id = (seed % layout.seats()) as u32;
+            let pass = layout.encode_id(id).unwrap();
+            prop_assert_eq!(pass.chars().count(), layout.pass_len());
+            let seat = layout.decode(&pass).unwrap();
+            prop_assert_eq!(seat.id, id);
+            prop_assert_eq!(layout.encode(seat.row, seat.column).unwrap(), pass);
+        }
+
+        #[test]
+        fn round_trip_passes(pass in "[FB]{7}[LR]{3}") {
+            let layout = SeatLayout::default();
+            let seat = layout.decode(&pass).unwrap();
+            prop_assert_eq!(layout.encode_id(seat.id).unwrap(), pass);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Seats mode: the row, column and id of every boarding pass, under a
+//! layout of choice for aircraft of other sizes.
+//!
+//! `day05 seats [LAYOUT OPTIONS] [--format text|json] [inputfile|-]`
+//!
+//! The layout options are `--rows N`, `--columns N`, `--row-letters XY` and
+//! `--column-letters XY`, see [`SeatLayout`]; the puzzle layout by default.
+
+use crate::{find_my_seat, Day05, Seat, SeatLayout};
+use serde_json::{json, Value};
+use utils::cli::{self, Format};
+use utils::{Input, Result, Solution, Source};
+
+// the layout options of every mode
+pub(crate) struct LayoutArgs {
+    rows: usize,
+    columns: usize,
+    row_letters: [char; 2],
+    column_letters: [char; 2],
+}
+
+impl Default for LayoutArgs {
+    fn default() -> LayoutArgs {
+        let layout = SeatLayout::default();
+        LayoutArgs {
+            rows: layout.rows(),
+            columns: layout.columns(),
+            row_letters: layout.row_letters(),
+            column_letters: layout.column_letters(),
+        }
+    }
+}
+
+pub(crate) const LAYOUT_USAGE: &str =
+    "--rows, --columns: the number of row and column characters (7 and 3)
+--row-letters, --column-letters: the letters of the lower and the upper half
+(FB and LR)";
+
+// two letters, the lower half first
+fn parse_letters(name: &str, value: &str) -> std::result::Result<[char; 2], String> {
+    match value.chars().collect::<Vec<char>>()[..] {
+        [lower, upper] => Ok([lower, upper]),
+        _ => Err(format!("invalid {} '{}', expect two letters", name, value)),
+    }
+}
+
+fn parse_count(name: &str, value: &str) -> std::result::Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("invalid {} '{}'", name, value))
+}
+
+impl LayoutArgs {
+    // takes the argument, and its value from the iterator, if it is a
+    // layout option
+    pub(crate) fn parse_arg<'a>(
+        &mut self,
+        arg: &str,
+        it: &mut impl Iterator<Item = &'a String>,
+    ) -> std::result::Result<bool, String> {
+        match arg {
+            "--rows" => self.rows = parse_count(arg, cli::option_value(arg, it)?)?,
+            "--columns" => self.columns = parse_count(arg, cli::option_value(arg, it)?)?,
+            "--row-letters" => self.row_letters = parse_letters(arg, cli::option_value(arg, it)?)?,
+            "--column-letters" => {
+                self.column_letters = parse_letters(arg, cli::option_value(arg, it)?)?
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn layout(&self) -> std::result::Result<SeatLayout, String> {
+        SeatLayout::new(
+            self.rows,
+            self.columns,
+            self.row_letters,
+            self.column_letters,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// The seats as a table of pass, row, column and id, then the highest id
+/// and the free seat (`none` if there is none).
+pub fn to_text(passes: &[&str], seats: &[Seat], mine: Option<u32>) -> String {
+    let width = passes
+        .iter()
+        .map(|p| p.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut out = format!(
+        "{:<w$} {:>6} {:>6} {:>10}\n",
+        "pass",
+        "row",
+        "column",
+        "id",
+        w = width
+    );
+    for (pass, seat) in passes.iter().zip(seats.iter()) {
+        out.push_str(&format!(
+            "{:<w$} {:>6} {:>6} {:>10}\n",
+            pass,
+            seat.row,
+            seat.column,
+            seat.id,
+            w = width
+        ));
+    }
+    let highest = seats.iter().map(|seat| seat.id).max();
+    let show = |id: Option<u32>| id.map_or("none".to_string(), |id| id.to_string());
+    out.push_str(&format!(
+        "highest id: {}\nfree seat: {}\n",
+        show(highest),
+        show(mine)
+    ));
+    out
+}
+
+/// The seats as `{"seats": [{"pass", "row", "column", "id"}], "highest", "free"}`.
+pub fn to_json(passes: &[&str], seats: &[Seat], mine: Option<u32>) -> Value {
+    let rows: Vec<Value> = passes
+        .iter()
+        .zip(seats.iter())
+        .map(|(pass, seat)| {
+            json!({
+                "pass": pass,
+                "row": seat.row,
+                "column": seat.column,
+                "id": seat.id,
+            })
+        })
+        .collect();
+    json!({
+        "seats": rows,
+        "highest": seats.iter().map(|seat| seat.id).max(),
+        "free": mine,
+    })
+}
+
+struct Args {
+    layout: SeatLayout,
+    format: Format,
+    source: Source,
+}
+
+const USAGE: &str = "usage: day05 seats [--rows N] [--columns N] [--row-letters XY]
+                   [--column-letters XY] [--format text|json] [inputfile|-]
+decodes every boarding pass into its row, column and seat id";
+
+fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut layout = LayoutArgs::default();
+    let mut format = Format::Text;
+    let mut source = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--format" => format = Format::parse_arg(cli::option_value(arg, &mut it)?)?,
+            opt if layout.parse_arg(opt, &mut it)? => (),
+            arg => cli::source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        layout: layout.layout()?,
+        format,
+        source: source.unwrap_or(Source::Stdin),
+    })
+}
+
+fn run_seats(args: &Args) -> Result<String> {
+    let input = Input::read(&args.source, Day05.read_options())?;
+    let seats = args
+        .layout
+        .decode_all(input.lines())
+        .map_err(|e| e.in_file(input.name()))?;
+    let passes: Vec<&str> = input.texts().collect();
+    let mine = find_my_seat(&seats, &args.layout).ok();
+    Ok(match args.format {
+        Format::Text => to_text(&passes, &seats, mine),
+        Format::Json => format!("{}\n", to_json(&passes, &seats, mine)),
+    })
+}
+
+/// Entry point of `day05 seats`, returns the exit status.
+pub fn main(args: &[String]) -> i32 {
+    cli::run_subcommand(
+        args,
+        parse_args,
+        run_seats,
+        &format!("{}\n{}", USAGE, LAYOUT_USAGE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_layout_args() {
+        let args = parse_args(&argv("--rows 2 --row-letters UD in.txt")).unwrap();
+        assert_eq!(
+            args.layout,
+            SeatLayout::new(2, 3, ['U', 'D'], ['L', 'R']).unwrap()
+        );
+        assert!(parse_args(&argv("--row-letters U")).is_err());
+        assert!(parse_args(&argv("--columns x")).is_err());
+        assert_eq!(
+            parse_args(&argv("--rows 0")).err().unwrap(),
+            "validation error: a layout needs at least one row and one column character"
+        );
+    }
+
+    #[test]
+    fn test_output() {
+        let layout = SeatLayout::default();
+        let passes = ["BFFFBBFRRR", "FFFBBBFRRR"];
+        let seats: Vec<Seat> = passes.iter().map(|p| layout.decode(p).unwrap()).collect();
+        assert_eq!(
+            to_text(&passes, &seats, None),
+            "pass          row column         id
+BFFFBBFRRR     70      7        567
+FFFBBBFRRR     14      7        119
+highest id: 567
+free seat: none
+"
+        );
+        let json = to_json(&passes, &seats, Some(120));
+        assert_eq!(json["seats"][0]["row"], 70);
+        assert_eq!(json["seats"][1]["pass"], "FFFBBBFRRR");
+        assert_eq!(json["highest"], 567);
+        assert_eq!(json["free"], 120);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use utils::{Input, ReadOptions, Result, Solution, Trim};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End)
+    }
+
+    fn parse(&self, input: &Input) -> Result<Vec<String>> {
+        Ok(input.texts().map(String::from).collect())
+    }
+
+    fn part_one(&self, lines: &Vec<String>) -> Result<usize> {
+        process_lines(lines, false)
+    }
+
+    fn part_two(&self, lines: &Vec<String>) -> Result<usize> {
+        process_lines(lines, true)
+    }
+}
+
+fn process_lines<I, S>(lines_it: I, part_two: bool) -> Result<usize>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut total = 0;
+    let mut uniq = HashSet::new();
+    let mut empty_intersection: bool = false;
+    for maybe_line in lines_it {
+        let line = maybe_line.as_ref();
+        if line.trim().is_empty() {
+            // end of record, add set count to total, reset
+            total += uniq.len();
+            uniq.clear();
+            empty_intersection = false;
+            continue;
+        }
+        if part_two && empty_intersection {
+            continue; // this record has no questions to which everyone answered "yes"
+        }
+
+        if uniq.is_empty() {
+            // first line of record
+            uniq = line.bytes().collect();
+            continue;
+        }
+        let newset = line.bytes().collect(); // unique letters in this line
+        if part_two {
+            uniq = uniq.intersection(&newset).copied().collect();
+            // if the intersection is empty, set flag to signal empty intersection of record lines
+            if uniq.is_empty() {
+                empty_intersection = true;
+            }
+        } else {
+            // part one - set union
+            uniq = uniq.union(&newset).copied().collect();
+        }
+    }
+    Ok(total + uniq.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_lines() {
+        let lines = [
+            "abc", "", "a", "b", "c", "", "ab", "ac", "", "a", "a", "a", "a", "", "b",
+        ];
+        assert_eq!(process_lines(lines.iter(), false).unwrap(), 11);
+        assert_eq!(process_lines(lines.iter(), true).unwrap(), 6);
+    }
+}
@@ -0,0 +1,109 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use utils::{Error, Input, ReadOptions, Result, Solution, Trim};
+
+#[derive(Debug)]
+pub struct BagRec {
+    pub color: String,
+    pub num: u32, // quantity of bags of this color allowed
+}
+
+// a color and the bags it contains, None for "contain no other bags"
+type Rule = (String, Option<Vec<BagRec>>);
+
+lazy_static! {
+    static ref RULE_RE: Regex =
+        Regex::new(r"^(?P<c1>[[:alpha:]]+ [[:alpha:]]+) bags contain (?P<rest>.+?)\.?$").unwrap();
+    static ref BAG_RE: Regex =
+        Regex::new(r"^(?P<num>\d+) (?P<color>[[:alpha:]]+ [[:alpha:]]+) bags?$").unwrap();
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = HashMap<String, Vec<BagRec>>;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<HashMap<String, Vec<BagRec>>> {
+        let mut rules = HashMap::new();
+        for line in input.lines() {
+            let (color, contents) = parse_line(&line.text).map_err(|e| e.at_line(line.number))?;
+            rules.insert(color, contents.unwrap_or_default());
+        }
+        Ok(rules)
+    }
+
+    fn part_one(&self, _rules: &HashMap<String, Vec<BagRec>>) -> Result<usize> {
+        Err(Error::no_solution("day 7 is not solved yet"))
+    }
+
+    fn part_two(&self, _rules: &HashMap<String, Vec<BagRec>>) -> Result<u64> {
+        Err(Error::no_solution("day 7 is not solved yet"))
+    }
+}
+
+fn parse_line(line: &str) -> Result<Rule> {
+    let caps = RULE_RE
+        .captures(line)
+        .ok_or_else(|| Error::parse("expect '<color> bags contain ...'").at_column(1))?;
+    let c1 = caps["c1"].to_string();
+    let rest = caps.name("rest").unwrap();
+    if rest.as_str() == "no other bags" {
+        return Ok((c1, None));
+    }
+    let mut contents = Vec::new();
+    let mut col = rest.start() + 1;
+    for part in rest.as_str().split(", ") {
+        let bag = BAG_RE.captures(part).ok_or_else(|| {
+            Error::parse(format!("expect '<n> <color> bags', got {:?}", part)).at_column(col)
+        })?;
+        let num = bag["num"]
+            .parse::<u32>()
+            .map_err(|e| Error::from(e).at_column(col))?;
+        contents.push(BagRec {
+            color: bag["color"].to_string(),
+            num,
+        });
+        col += part.len() + 2;
+    }
+    Ok((c1, Some(contents)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        let res = parse_line("light salmon bags contain no other bags.").unwrap();
+        assert_eq!(res.0, "light salmon");
+        assert!(res.1.is_none());
+        let res = parse_line("light salmon bags contain 5 dotted olive bags, 4 wavy lavender bags")
+            .unwrap();
+        assert_eq!(res.0, "light salmon");
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = Input::from_text(include_str!("../small.txt"), Day07.read_options());
+        let rules = Day07.parse(&input).unwrap();
+        assert_eq!(rules.len(), 9);
+        assert_eq!(rules["muted yellow"][1].color, "faded blue");
+        assert_eq!(rules["muted yellow"][1].num, 9);
+        assert!(rules["dotted black"].is_empty());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err =
+            parse_line("dark red bags contain 2 dark orange bags, x bright red bags.").unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(43));
+    }
+}
@@ -0,0 +1,85 @@
+use utils::{Error, Input, ReadOptions, Result, Solution, Trim};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeatType {
+    Occupied,
+    Empty,
+    Floor,
+}
+
+pub type Grid = Vec<Vec<SeatType>>;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = Grid;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Grid> {
+        let mut grid = Grid::new();
+        for line in input.lines() {
+            let row = line2rec(&line.text).map_err(|e| e.at_line(line.number))?;
+            if !grid.is_empty() && row.len() != grid[0].len() {
+                return Err(Error::validation("all the rows must have the same length")
+                    .at_line(line.number)
+                    .at_column(1));
+            }
+            grid.push(row);
+        }
+        Ok(grid)
+    }
+
+    fn part_one(&self, _grid: &Grid) -> Result<usize> {
+        Err(Error::no_solution("day 11 is not solved yet"))
+    }
+
+    fn part_two(&self, _grid: &Grid) -> Result<usize> {
+        Err(Error::no_solution("day 11 is not solved yet"))
+    }
+}
+
+fn line2rec(line: &str) -> Result<Vec<SeatType>> {
+    let mut row = Vec::with_capacity(line.len());
+    for (i, b) in line.bytes().enumerate() {
+        match b {
+            b'L' => row.push(SeatType::Empty),
+            b'#' => row.push(SeatType::Occupied),
+            b'.' => row.push(SeatType::Floor),
+            _ => return Err(Error::parse("invalid character").at_column(i + 1)),
+        };
+    }
+    Ok(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid {
+        Day11
+            .parse(&Input::from_text(text, Day11.read_options()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid(include_str!("../small.txt"));
+        assert_eq!((g.len(), g[0].len()), (10, 10));
+        assert_eq!(
+            &g[0][..3],
+            &[SeatType::Empty, SeatType::Floor, SeatType::Empty]
+        );
+        let input = Input::from_text("L.L\nL.\n", Day11.read_options());
+        let err = Day11.parse(&input).unwrap_err();
+        assert_eq!(err.location().unwrap().line, Some(2));
+        let input = Input::from_text("L.x\n", Day11.read_options());
+        let err = Day11.parse(&input).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(3));
+    }
+}
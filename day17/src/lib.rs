@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::fmt;
+use utils::{Alternative, Error, Input, Part, ReadOptions, Result, Solution, Trim};
+
+pub mod part2;
+pub mod part2_slow;
+
+pub const CYCLES: usize = 6;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    // (row, column) of the active cubes of the initial slice
+    type Parsed = Vec<(i32, i32)>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Vec<(i32, i32)>> {
+        let mut cells = Vec::new();
+        for (rowidx, line) in input.lines().iter().enumerate() {
+            let points =
+                parse_line(&line.text, rowidx as u32).map_err(|e| e.at_line(line.number))?;
+            cells.extend(points.into_iter().map(|p| (p.x, p.y)));
+        }
+        Ok(cells)
+    }
+
+    fn part_one(&self, cells: &Vec<(i32, i32)>) -> Result<u32> {
+        let recs: HashSet<Point> = cells.iter().map(|&(x, y)| Point { x, y, z: 0 }).collect();
+        let mut grid = Grid::new(recs);
+        for _ in 0..CYCLES {
+            grid.cycle();
+        }
+        Ok(grid.count())
+    }
+
+    fn part_two(&self, cells: &Vec<(i32, i32)>) -> Result<u32> {
+        Ok(part2::run(cells, CYCLES))
+    }
+
+    fn alternatives(&self) -> Vec<Alternative<Vec<(i32, i32)>>> {
+        vec![Alternative {
+            name: "slow",
+            part: Part::Two,
+            solve: |cells| Ok(part2_slow::run(cells, CYCLES).to_string()),
+        }]
+    }
+}
+
+#[derive(Eq, Hash, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+struct Grid {
+    recs: HashSet<Point>
+}
+
+impl Grid {
+    fn new(recs_ : HashSet<Point>) -> Grid {
+        Grid{recs: recs_}
+    }
+    fn count(&self) -> u32 {
+        self.recs.len().try_into().unwrap()
+    }
+    fn cycle(&mut self) -> u32 {
+        let mut cands : HashSet<Point> = HashSet::new();
+        for p in &self.recs {
+            for x in p.x-1..p.x+2 {
+                for y in p.y-1..p.y+2 {
+                    for z  in p.z-1..p.z+2 {
+                        cands.insert(Point{x,y,z});
+                    }
+                }
+            }
+        }
+    
+        let mut addlist : Vec<Point> = Vec::new();
+        let mut removelist : Vec<Point> = Vec::new();
+        for p in cands {
+            let active = self.recs.contains(&p);
+            let n = self.check_neighbors(&p);
+            match (active, n) {
+                (true, 2) | (true, 3) => (),
+                (true, _) => removelist.push(p),
+                (false, 3) => addlist.push(p),
+                (false, _) => (),
+            };
+        }
+        for p in removelist.iter() {
+            self.recs.remove(p);
+        }    
+        for p in addlist.into_iter() {
+            self.recs.insert(p);
+        }
+        self.count()
+    }
+    fn check_neighbors(&self, p : &Point) -> u32 {
+        let mut cnt = 0;
+        for x in p.x-1..p.x+2 {
+            for y in p.y-1..p.y+2 {
+                for z  in p.z-1..p.z+2 {
+                    // skip own coordinates
+                    if x == p.x && y == p.y && z == p.z {
+                        continue
+                    }
+                    if self.recs.contains(&Point{x,y,z}) {
+                        cnt += 1;
+                    }
+                }
+            }
+        }
+        cnt
+    }
+}
+
+fn parse_line(line: &str, rowidx: u32) -> Result<Vec<Point>> {
+    if line.is_empty() {
+        return Err(Error::parse("empty input line"));
+    }
+    let mut res: Vec<Point> = Vec::new();
+    for (i, b) in line.as_bytes().iter().enumerate() {
+        match b {
+            b'#' => res.push(Point {
+                x: rowidx as i32,
+                y: i as i32,
+                z: 0,
+            }),
+            b'.' => (),
+            _ => return Err(Error::parse("invalid character").at_column(i + 1)),
+        };
+    }
+    Ok(res)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small() {
+        let input = Input::from_text(".#.\n..#\n###\n", Day17.read_options());
+        let cells = Day17.parse(&input).unwrap();
+        assert_eq!(Day17.part_one(&cells).unwrap(), 112);
+        assert_eq!(Day17.part_two(&cells).unwrap(), 848);
+        assert_eq!(part2_slow::run(&cells, CYCLES), 848);
+    }
+}
@@ -1,117 +1,3 @@
-use std::collections::HashSet;
-use std::convert::TryInto;
-use std::error::Error;
-use std::fmt;
-use std::io::BufRead;
-
-#[derive(Eq, Hash, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({},{},{})", self.x, self.y, self.z)
-    }
-}
-struct Grid {
-    recs: HashSet<Point>
-}
-
-impl Grid {
-    fn new(recs_ : HashSet<Point>) -> Grid {
-        Grid{recs: recs_}
-    }
-    fn count(&self) -> u32 {
-        self.recs.len().try_into().unwrap()
-    }
-    fn cycle(&mut self) -> u32 {
-        let mut cands : HashSet<Point> = HashSet::new();
-        for p in &self.recs {
-            for x in p.x-1..p.x+2 {
-                for y in p.y-1..p.y+2 {
-                    for z  in p.z-1..p.z+2 {
-                        cands.insert(Point{x,y,z});
-                    }
-                }
-            }
-        }
-    
-        let mut addlist : Vec<Point> = Vec::new();
-        let mut removelist : Vec<Point> = Vec::new();
-        for p in cands {
-            let active = self.recs.contains(&p);
-            let n = self.check_neighbors(&p);
-            match (active, n) {
-                (true, 2) | (true, 3) => (),
-                (true, _) => removelist.push(p),
-                (false, 3) => addlist.push(p),
-                (false, _) => (),
-            };
-        }
-        for p in removelist.iter() {
-            self.recs.remove(p);
-        }    
-        for p in addlist.into_iter() {
-            self.recs.insert(p);
-        }
-        self.count()
-    }
-    fn check_neighbors(&self, p : &Point) -> u32 {
-        let mut cnt = 0;
-        for x in p.x-1..p.x+2 {
-            for y in p.y-1..p.y+2 {
-                for z  in p.z-1..p.z+2 {
-                    // skip own coordinates
-                    if x == p.x && y == p.y && z == p.z {
-                        continue
-                    }
-                    if self.recs.contains(&Point{x,y,z}) {
-                        cnt += 1;
-                    }
-                }
-            }
-        }
-        cnt
-    }
-}
-
-fn parse_line(line: &str, rowidx: u32) -> Result<Vec<Point>, Box<dyn Error>> {
-    if line.len() == 0 {
-        return Err("empty input line".into());
-    }
-    let mut res: Vec<Point> = Vec::new();
-    for (i, b) in line.as_bytes().iter().enumerate() {
-        match b {
-            b'#' => res.push(Point {
-                x: rowidx as i32,
-                y: i as i32,
-                z: 0,
-            }),
-            b'.' => (),
-            _ => return Err("invalid character".into()),
-        };
-    }
-    Ok(res)
-}
 fn main() {
-    let mut cnt: u32 = 0;
-    let recs: HashSet<Point> = std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .map(|l| {
-            let v = parse_line(&l, cnt);
-            cnt += 1;
-            v
-        })
-        .filter_map(Result::ok)
-        .flatten()
-        .collect();
-    let mut grid = Grid::new(recs);
-    for i in 0..6 { // FIXME - change to 6
-        println!("{} - {}", i+1, grid.cycle());
-    }
+    utils::cli::run(&day17::Day17);
 }
@@ -1,7 +1,5 @@
 use std::collections::HashSet;
 use std::convert::TryInto;
-use std::error::Error;
-use std::io::BufRead;
 
 type PointX = (i32,i32,i32,i32);
 
@@ -74,36 +72,13 @@ impl GridX {
     }
 }
 
-fn parse_line(line: &str, rowidx: u32) -> Result<Vec<PointX>, Box<dyn Error>> {
-    if line.len() == 0 {
-        return Err("empty input line".into());
-    }
-    let mut res: Vec<PointX> = Vec::new();
-    for (i, b) in line.as_bytes().iter().enumerate() {
-        match b {
-            b'#' => res.push((rowidx as i32,i as i32,0,0)),
-            b'.' => (),
-            _ => return Err("invalid character".into()),
-        };
-    }
-    Ok(res)
-}
-fn main() {
-    let mut cnt: u32 = 0;
-    let recs: HashSet<PointX> = std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .map(|l| {
-            let v = parse_line(&l, cnt);
-            cnt += 1;
-            v
-        })
-        .filter_map(Result::ok)
-        .flatten()
-        .collect();
+// run starts from the active (row, column) cells of the initial slice
+// and returns the number of active cubes after <cycles> cycles.
+pub fn run(cells: &[(i32, i32)], cycles: usize) -> u32 {
+    let recs: HashSet<PointX> = cells.iter().map(|&(x, y)| (x, y, 0, 0)).collect();
     let mut grid = GridX::new(recs);
-    for i in 0..6 { // FIXME - change to 6
-        println!("{} - {}", i+1, grid.cycle());
+    for _ in 0..cycles {
+        grid.cycle();
     }
+    grid.count()
 }
@@ -1,8 +1,6 @@
 use std::collections::HashSet;
 use std::convert::TryInto;
-use std::error::Error;
 use std::fmt;
-use std::io::BufRead;
 
 #[derive(Eq, Hash, PartialEq)]
 struct PointX {
@@ -77,41 +75,16 @@ impl GridX {
     }
 }
 
-fn parse_line(line: &str, rowidx: u32) -> Result<Vec<PointX>, Box<dyn Error>> {
-    if line.len() == 0 {
-        return Err("empty input line".into());
-    }
-    let mut res: Vec<PointX> = Vec::new();
-    for (i, b) in line.as_bytes().iter().enumerate() {
-        match b {
-            b'#' => res.push(PointX {
-                x: rowidx as i32,
-                y: i as i32,
-                z: 0,
-                w: 0,
-            }),
-            b'.' => (),
-            _ => return Err("invalid character".into()),
-        };
-    }
-    Ok(res)
-}
-fn main() {
-    let mut cnt: u32 = 0;
-    let recs: HashSet<PointX> = std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .map(|l| {
-            let v = parse_line(&l, cnt);
-            cnt += 1;
-            v
-        })
-        .filter_map(Result::ok)
-        .flatten()
+// run is the same as part2::run, with a cycle() that checks the neighbors
+// of every candidate point while visiting the active ones.
+pub fn run(cells: &[(i32, i32)], cycles: usize) -> u32 {
+    let recs: HashSet<PointX> = cells
+        .iter()
+        .map(|&(x, y)| PointX { x, y, z: 0, w: 0 })
         .collect();
     let mut grid = GridX::new(recs);
-    for i in 0..6 { // FIXME - change to 6
-        println!("{} - {}", i+1, grid.cycle());
+    for _ in 0..cycles {
+        grid.cycle();
     }
+    grid.count()
 }
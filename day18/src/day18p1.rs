@@ -1,5 +1,4 @@
-use std::error::Error;
-use std::io::BufRead;
+use utils::{Error, Result};
 
 #[derive(Copy, Clone, Debug)]
 enum Token {
@@ -22,7 +21,7 @@ enum ParseState {
 //     }
 // }
 
-fn reduce(stack: &mut Vec<Token>) -> Result<(), Box<dyn Error>> {
+fn reduce(stack: &mut Vec<Token>) -> Result<()> {
     if stack.len() < 3 {
         return Ok(());
     }
@@ -67,22 +66,19 @@ fn reduce(stack: &mut Vec<Token>) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn eval(line: &str) -> Result<i64, Box<dyn Error>> {
+pub fn eval(line: &str) -> Result<i64> {
     let mut stack: Vec<Token> = Vec::new();
     let mut acc: i64 = 0;
     let mut state: ParseState = ParseState::None;
-    for b in line.as_bytes().iter() {
+    for (i, b) in line.as_bytes().iter().enumerate() {
         match b {
             b'(' => stack.push(Token::ParenLeft),
             b')' => {
-                match state {
-                    ParseState::InDigit => {
-                        stack.push(Token::Literal(acc));
-                        acc = 0;
-                        reduce(&mut stack)?;
-                    }
-                    _ => (),
-                };
+                if let ParseState::InDigit = state {
+                    stack.push(Token::Literal(acc));
+                    acc = 0;
+                    reduce(&mut stack)?;
+                }
                 state = ParseState::None;
                 stack.push(Token::ParenRight);
                 reduce(&mut stack)?;
@@ -93,48 +89,33 @@ fn eval(line: &str) -> Result<i64, Box<dyn Error>> {
             b'-' => stack.push(Token::Minus),
             b'*' => stack.push(Token::Mult),
             b' ' | b'\t' => {
-                match state {
-                    ParseState::InDigit => {
-                        stack.push(Token::Literal(acc));
-                        acc = 0;
-                        reduce(&mut stack)?;
-                    }
-                    _ => (),
-                };
+                if let ParseState::InDigit = state {
+                    stack.push(Token::Literal(acc));
+                    acc = 0;
+                    reduce(&mut stack)?;
+                }
                 state = ParseState::None;
             }
             b'0'..=b'9' => {
                 acc = (10 * acc) + *b as i64 - 48; // ascii convertion to digit
                 state = ParseState::InDigit;
             }
-            _ => return Err("bad input character".into()),
+            _ => return Err(Error::parse("bad input character").at_column(i + 1)),
         };
     }
-    match state {
-        ParseState::InDigit => stack.push(Token::Literal(acc)),
-        _ => (),
-    };
+    if let ParseState::InDigit = state {
+        stack.push(Token::Literal(acc));
+    }
     reduce(&mut stack)?;
     if stack.len() != 1 {
-        return Err("failure in eval - malformed input".into());
+        return Err(Error::parse("failure in eval - malformed input").at_column(line.len()));
     }
     match stack[0] {
         Token::Literal(n) => Ok(n),
-        _ => Err("bad input".into()),
+        _ => Err(Error::parse("bad input").at_column(line.len())),
     }
 }
 
-fn main() {
-    let result: i64 = std::io::stdin()
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .map(|line| eval(&line))
-        .filter_map(Result::ok)
-        .sum();
-    println!("sum: {}", result);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
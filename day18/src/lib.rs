@@ -0,0 +1,215 @@
+use utils::{Error, Input, Line, ReadOptions, Result, Solution, Trim};
+
+mod day18p1;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Parsed = Vec<Line>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End).skip_empty()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Vec<Line>> {
+        Ok(input.lines().to_vec())
+    }
+
+    // operators have the same precedence, evaluated left to right
+    fn part_one(&self, lines: &Vec<Line>) -> Result<i64> {
+        let mut result: i64 = 0;
+        for line in lines.iter() {
+            result += day18p1::eval(&line.text).map_err(|e| e.at_line(line.number))?;
+        }
+        Ok(result)
+    }
+
+    // addition is evaluated before multiplication
+    fn part_two(&self, lines: &Vec<Line>) -> Result<i64> {
+        let mut result: i64 = 0;
+        for line in lines.iter() {
+            result += eval(line.text.as_bytes()).map_err(|e| e.at_line(line.number))?;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Token {
+    Literal(i64),
+    Plus,
+    Mult,
+}
+
+fn find_closing_paren(inp: &[u8]) -> Result<usize> {
+    let mut cnt: u16 = 0;
+    for (i, b) in inp.iter().enumerate() {
+        match b {
+            b'(' => cnt += 1,
+            b')' => {
+                cnt -= 1;
+                if cnt == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => (),
+        }
+    }
+    Err(Error::parse("closing parens not found").at_column(1))
+}
+
+// return the index after the end of the number
+fn index_past_number(inp: &[u8]) -> usize {
+    for (i, b) in inp.iter().enumerate().skip(1) {
+        match b {
+            b'0'..=b'9' => continue,
+            _ => return i,
+        };
+    }
+    inp.len()
+}
+
+fn reduce(stack: &mut Vec<Token>) -> Result<()> {
+    if stack.len() < 3 {
+        return Err(Error::parse("stack len < 3"));
+    }
+    loop {
+        if stack.len() < 3 {
+            return Ok(());
+        }
+        let c = stack[stack.len() - 1];
+        let b = stack[stack.len() - 2];
+        let a = stack[stack.len() - 3];
+        match (a, b, c) {
+            (Token::Literal(n), Token::Plus, Token::Literal(m)) => {
+                stack.pop();
+                stack.pop();
+                stack.pop();
+                stack.push(Token::Literal(n + m));
+            }
+            _ => return Ok(()),
+        };
+    } // loop
+}
+
+fn eval(inp: &[u8]) -> Result<i64> {
+    eval_from(inp, 0)
+}
+
+// eval_from evaluates inp, which starts at byte offset <start> of the line;
+// the offset is only used to report the column of errors.
+fn eval_from(inp: &[u8], start: usize) -> Result<i64> {
+    let mut i: usize = 0;
+    let mut stack: Vec<Token> = Vec::new();
+
+    loop {
+        if i >= inp.len() {
+            break;
+        }
+        match inp[i] {
+            b'(' => {
+                let ind = find_closing_paren(&inp[i..]).map_err(|e| e.at_column(start + i + 1))?;
+                let val = eval_from(&inp[i + 1..(i + ind)], start + i + 1)?;
+                stack.push(Token::Literal(val));
+                if stack.len() >= 3 {
+                    if let Token::Plus = stack[stack.len() - 2] {
+                        reduce(&mut stack).map_err(|e| e.at_column(start + i + 1))?;
+                    }
+                }
+                i += ind; // additional +1 is after match
+            }
+            b')' => return Err(Error::parse("unbalanced ')'").at_column(start + i + 1)),
+            b'+' => stack.push(Token::Plus),
+            b'*' => stack.push(Token::Mult),
+            b'0'..=b'9' => {
+                let ind = index_past_number(&inp[i..]);
+                let mut acc: i64 = 0;
+                for b in inp[i..i + ind].iter() {
+                    acc = (10 * acc) + *b as i64 - 48;
+                }
+                stack.push(Token::Literal(acc));
+                if stack.len() >= 3 {
+                    if let Token::Plus = stack[stack.len() - 2] {
+                        reduce(&mut stack).map_err(|e| e.at_column(start + i + 1))?;
+                    }
+                }
+            }
+            b' ' => (),
+            _ => return Err(Error::parse("unknown character").at_column(start + i + 1)),
+        };
+        i += 1;
+    } // loop
+
+    // we just need to multiply through whatever is left
+    let mut acc = 1;
+    for t in stack.iter() {
+        match t {
+            Token::Literal(n) => acc *= n,
+            Token::Mult => (),
+            _ => {
+                return Err(
+                    Error::parse("bad token at the end of eval - not number or '*'")
+                        .at_column(start + inp.len()),
+                )
+            }
+        }
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_closing_parens() {
+        let inp = "((2+3) * 4) + 5";
+        assert_eq!(10, find_closing_paren(inp.as_bytes()).unwrap());
+        assert_eq!(4, find_closing_paren(&inp.as_bytes()[1..]).unwrap());
+    }
+
+    #[test]
+    fn test_index_past_number() {
+        assert_eq!(1, index_past_number("9".as_bytes()));
+        assert_eq!(2, index_past_number("99 +".as_bytes()));
+        assert_eq!(4, index_past_number("3570)".as_bytes()));
+        assert_eq!(4, index_past_number("3570".as_bytes()));
+    }
+    #[test]
+    fn test_eval() {
+        assert_eq!(2, eval("(2)".as_bytes()).unwrap());
+        assert_eq!(3, eval("1 + 2".as_bytes()).unwrap());
+        assert_eq!(20, eval("2 * 3 + 7".as_bytes()).unwrap());
+        assert_eq!(
+            1445,
+            eval("5 + (8 * 3 + 9 + 3 * 4 * 3)".as_bytes()).unwrap()
+        );
+        assert_eq!(51, eval("1 + (2 * 3) + (4 * (5 + 6))".as_bytes()).unwrap());
+        assert_eq!(46, eval("2 * 3 + (4 * 5)".as_bytes()).unwrap());
+        assert_eq!(
+            1445,
+            eval("5 + (8 * 3 + 9 + 3 * 4 * 3)".as_bytes()).unwrap()
+        );
+        assert_eq!(
+            669060,
+            eval("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))".as_bytes()).unwrap()
+        );
+        assert_eq!(
+            23340,
+            eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2".as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eval_error_column() {
+        let err = eval("1 + (2 * x)".as_bytes()).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(10));
+        let err = eval("(1 + 2".as_bytes()).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(1));
+        let err = eval("2 * (3 + 4))".as_bytes()).unwrap_err();
+        assert_eq!(err.location().unwrap().column, Some(12));
+    }
+}
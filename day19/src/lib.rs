@@ -0,0 +1,143 @@
+use regex::Regex;
+use std::collections::HashMap;
+use utils::{Error, Input, ReadOptions, Result, Solution, Trim};
+
+mod day19p1;
+
+pub struct Day19;
+
+/// The rules and the received messages.
+pub struct Notes {
+    pub rules: HashMap<String, String>,
+    pub messages: Vec<String>,
+}
+
+enum State {
+    Rules,
+    Messages,
+}
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Parsed = Notes;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default().trim(Trim::End)
+    }
+
+    // rules come first, then an empty line and the messages
+    fn parse(&self, input: &Input) -> Result<Notes> {
+        let mut rules: HashMap<String, String> = HashMap::new();
+        let mut messages = Vec::new();
+        let mut state = State::Rules;
+        for line in input.lines() {
+            match state {
+                State::Rules if line.text.is_empty() => state = State::Messages,
+                State::Rules => {
+                    add_rule(&line.text, &mut rules).map_err(|e| e.at_line(line.number))?
+                }
+                State::Messages if line.text.is_empty() => (),
+                State::Messages => messages.push(line.text.clone()),
+            };
+        }
+        Ok(Notes { rules, messages })
+    }
+
+    fn part_one(&self, notes: &Notes) -> Result<usize> {
+        count_matches(&day19p1::make_regexp_wrap(&notes.rules)?, &notes.messages)
+    }
+
+    // rules 8 and 11 loop, see make_regexp
+    fn part_two(&self, notes: &Notes) -> Result<usize> {
+        count_matches(&make_regexp_wrap(&notes.rules)?, &notes.messages)
+    }
+}
+
+fn count_matches(rexp: &str, messages: &[String]) -> Result<usize> {
+    let re = Regex::new(rexp).map_err(|e| Error::validation(format!("invalid regexp: {}", e)))?;
+    Ok(messages.iter().filter(|m| re.is_match(m)).count())
+}
+
+fn add_rule(line: &str, rules: &mut HashMap<String, String>) -> Result<()> {
+    let mut s = line.splitn(2, ": ");
+    let key = s.next().filter(|k| !k.is_empty());
+    let key = key.ok_or_else(|| Error::parse("bad input / key").at_column(1))?;
+    let val = s
+        .next()
+        .ok_or_else(|| Error::parse("bad input / value").at_column(key.len() + 1))?;
+    rules.insert(key.to_owned(), val.to_owned());
+    Ok(())
+}
+
+fn make_regexp_wrap(rules: &HashMap<String, String>) -> Result<String> {
+    let mut re = String::new();
+    let mut sep = String::new();
+    for num in ["1", "2", "3", "4"].iter() {
+        re.push_str(&sep);
+        re.push('(');
+        re.push('^');
+        re.push_str(&make_regexp("0", rules, num)?);
+        re.push('$');
+        re.push(')');
+        sep = "|".to_string();
+    }
+    Ok(re)
+}
+
+fn make_regexp(ind: &str, rules: &HashMap<String, String>, repeat: &str) -> Result<String> {
+    let rule = rules
+        .get(ind)
+        .ok_or_else(|| Error::validation(format!("no rule {:?}", ind)))?;
+    if rule == "\"a\"" {
+        return Ok("a".to_owned());
+    }
+    if rule == "\"b\"" {
+        return Ok("b".to_owned());
+    }
+
+    let mut regexp = String::new();
+    if ind == "11" {
+        let a = make_regexp("42", rules, repeat)?;
+        let b = make_regexp("31", rules, repeat)?;
+        regexp = format!("({}){{{}}}({}){{{}}}", &a, repeat, &b, repeat);
+        // println!("11: {}", regexp);
+        return Ok(regexp);
+    }
+    if !rule.contains('|') {
+        for s in rule.split_ascii_whitespace() {
+            let part = make_regexp(s, rules, repeat)?;
+            regexp.push_str(&part);
+        }
+        return Ok(regexp);
+    }
+    let mut sep = "";
+    regexp.push('(');
+    for p in rule.split('|') {
+        regexp.push_str(sep);
+        for s in p.split_ascii_whitespace() {
+            let part = make_regexp(s, rules, repeat)?;
+            regexp.push_str(&part);
+        }
+        sep = "|";
+    }
+    regexp.push(')');
+    if ind == "42" {
+        regexp.push('+');
+    }
+    Ok(regexp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_rule() {
+        let mut rules: HashMap<String, String> = HashMap::new();
+        let inp = "1: 2 3 | 3 2";
+        add_rule(inp, &mut rules).expect("failed to add rule");
+        assert_eq!(1, rules.len());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::error::Result;
+use crate::input::{Input, Source};
+use crate::report::{error_json, Report};
+use crate::solution::{Part, Runner, Solution};
+use std::io::{self, BufWriter, Write};
+use std::{env, process};
+
+/// Number of runs of `--time` when `--runs` is not given.
+pub const DEFAULT_RUNS: usize = 10;
+
+const USAGE: &str = "usage: dayNN [--time] [--runs N] [--format text|json] [inputfile|-]";
+
+/// Arguments of the day binaries.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub source: Source,
+    /// Number of timed runs, `None` unless `--time` is given.
+    pub time: Option<usize>,
+    pub format: Format,
+}
+
+/// Parses the arguments of a day binary, without the program name.
+/// The input is read from stdin when no file is given.
+pub fn parse_args(args: &[String]) -> std::result::Result<Args, String> {
+    let mut source = None;
+    let mut time = false;
+    let mut runs = DEFAULT_RUNS;
+    let mut format = Format::Text;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--time" => time = true,
+            "--runs" => {
+                runs = positive_arg(arg, option_value(arg, &mut it)?)?;
+                time = true;
+            }
+            "--format" => format = Format::parse_arg(option_value(arg, &mut it)?)?,
+            arg => source_arg(arg, &mut source)?,
+        }
+    }
+    Ok(Args {
+        source: source.unwrap_or(Source::Stdin),
+        time: if time { Some(runs) } else { None },
+        format,
+    })
+}
+
+/// The value of the option `name`, the next argument.
+pub fn option_value<'a>(
+    name: &str,
+    it: &mut impl Iterator<Item = &'a String>,
+) -> std::result::Result<&'a str, String> {
+    it.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("{} needs a value", name))
+}
+
+/// Takes an argument no option took: the input file, or `-` for stdin,
+/// given at most once. Other arguments starting with `--` are unknown
+/// options.
+pub fn source_arg(arg: &str, source: &mut Option<Source>) -> std::result::Result<(), String> {
+    if arg.starts_with("--") {
+        return Err(format!("unknown option '{}'", arg));
+    }
+    if source.is_some() {
+        return Err(format!("unexpected argument '{}'", arg));
+    }
+    *source = Some(Source::from_arg(arg));
+    Ok(())
+}
+
+/// Parses the value of the option `name`, a positive number such as the
+/// number of runs of `--runs`.
+pub fn positive_arg(name: &str, value: &str) -> std::result::Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!(
+            "invalid {} '{}', expected a positive number",
+            name, value
+        )),
+    }
+}
+
+/// Output format of the answers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// `part one: <answer>` lines, plus the timings with `--time`.
+    Text,
+    /// A single JSON object per run, see [`Report::to_json`].
+    Json,
+}
+
+impl Format {
+    /// Parses a `--format` argument: `text` or `json`.
+    pub fn parse_arg(arg: &str) -> std::result::Result<Format, String> {
+        match arg {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format '{}', expected text or json", arg)),
+        }
+    }
+}
+
+/// Output format of the modes writing a table of records, such as reports
+/// and exports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableFormat {
+    /// A header line, then a line per record, fields quoted by [`csv_field`].
+    Csv,
+    /// An array with an object per record.
+    Json,
+}
+
+impl TableFormat {
+    /// Parses a `--format` argument: `csv` or `json`.
+    pub fn parse_arg(arg: &str) -> std::result::Result<TableFormat, String> {
+        match arg {
+            "csv" => Ok(TableFormat::Csv),
+            "json" => Ok(TableFormat::Json),
+            _ => Err(format!("invalid format '{}', expected csv or json", arg)),
+        }
+    }
+}
+
+/// The field quoted for CSV if it holds a comma, a quote or a line break.
+pub fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Entry point shared by the day binaries:
+/// `dayNN [--time] [--runs N] [--format text|json] [inputfile|-]`.
+pub fn run<S: Solution>(solution: &S) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = parse_args(&args).unwrap_or_else(|msg| {
+        eprintln!("{}\n{}", msg, USAGE);
+        process::exit(2);
+    });
+    if !execute(solution, &args.source, &Part::BOTH, args.time, args.format) {
+        process::exit(1);
+    }
+}
+
+/// Reads the input with the runner's options and solves the given parts,
+/// timing `runs` repetitions of every step if given.
+pub fn run_source(
+    runner: &dyn Runner,
+    source: &Source,
+    parts: &[Part],
+    time: Option<usize>,
+) -> Result<Report> {
+    let input = Input::read(source, runner.options())?;
+    match time {
+        None => runner.run(&input, parts),
+        Some(runs) => runner.time(&input, parts, runs),
+    }
+}
+
+/// Entry point of a mode of a day binary, e.g. `day04 count`: parses the
+/// arguments with `parse`, then prints the output of `run`. Returns the exit
+/// status: 2 with the message and the usage if the arguments are wrong, 1
+/// with the error if the run fails.
+pub fn run_subcommand<A, P, R>(args: &[String], parse: P, run: R, usage: &str) -> i32
+where
+    P: FnOnce(&[String]) -> std::result::Result<A, String>,
+    R: FnOnce(&A) -> Result<String>,
+{
+    let run = |args: &A, out: &mut dyn Write| Ok(out.write_all(run(args)?.as_bytes())?);
+    stream_subcommand(args, parse, run, usage)
+}
+
+/// Like [`run_subcommand`] for modes writing to stdout as they go; what was
+/// written before an error is still printed.
+pub fn stream_subcommand<A, P, R>(args: &[String], parse: P, run: R, usage: &str) -> i32
+where
+    P: FnOnce(&[String]) -> std::result::Result<A, String>,
+    R: FnOnce(&A, &mut dyn Write) -> Result<()>,
+{
+    let args = match parse(args) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{}\n{}", msg, usage);
+            return 2;
+        }
+    };
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    match run(&args, &mut out).and_then(|()| Ok(out.flush()?)) {
+        Ok(()) => 0,
+        Err(err) => {
+            let _ = out.flush();
+            eprintln!("{}", err);
+            1
+        }
+    }
+}
+
+/// Runs and prints the report, or the error, in the given format.
+/// Returns false if the run failed.
+pub fn execute(
+    runner: &dyn Runner,
+    source: &Source,
+    parts: &[Part],
+    time: Option<usize>,
+    format: Format,
+) -> bool {
+    let result = run_source(runner, source, parts, time);
+    match (&result, format) {
+        (Ok(report), Format::Text) => print_report(report, time.is_some()),
+        (Ok(report), Format::Json) => println!("{}", report.to_json()),
+        (Err(err), Format::Text) => eprintln!("{}", err),
+        (Err(err), Format::Json) => {
+            println!("{}", error_json(runner.day(), &source.name(), err))
+        }
+    }
+    result.is_ok()
+}
+
+/// Prints one `part <n>: <answer>` line per part and the warnings on stderr.
+/// With `timed`, the time taken by the parsing and by every implementation
+/// of the parts follows, one per line.
+pub fn print_report(report: &Report, timed: bool) {
+    for warning in report.warnings.iter() {
+        eprintln!("{}", warning);
+    }
+    for result in report.answers() {
+        println!("part {}: {}", result.part, result.answer);
+    }
+    if !timed {
+        return;
+    }
+    let mut rows = vec![("parse".to_string(), report.parse.to_string())];
+    for result in report.results.iter() {
+        let label = match result.name {
+            None => format!("part {}", result.part),
+            Some(name) => format!("part {} ({})", result.part, name),
+        };
+        let mut stats = result.stats.to_string();
+        if report.answer(result.part) != Some(result.answer.as_str()) {
+            stats.push_str(&format!(", different answer {}", result.answer));
+        }
+        rows.push((label, stats));
+    }
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, stats) in rows.iter() {
+        println!("{:width$}  {}", label, stats, width = width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&args("")),
+            Ok(Args {
+                source: Source::Stdin,
+                time: None,
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("--time input.txt")),
+            Ok(Args {
+                source: Source::from_arg("input.txt"),
+                time: Some(DEFAULT_RUNS),
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("--format json")).unwrap().format,
+            Format::Json
+        );
+        assert!(parse_args(&args("--format xml")).is_err());
+        assert_eq!(parse_args(&args("- --runs 3")).unwrap().time, Some(3));
+        assert!(parse_args(&args("--runs 0")).is_err());
+        assert!(parse_args(&args("a b")).is_err());
+        assert!(parse_args(&args("--fast")).is_err());
+    }
+
+    #[test]
+    fn test_mode_args() {
+        let argv = args("--format json in.txt extra");
+        let mut it = argv.iter();
+        it.next();
+        assert_eq!(option_value("--format", &mut it), Ok("json"));
+        assert_eq!(
+            option_value("--format", &mut Vec::new().iter()),
+            Err("--format needs a value".to_string())
+        );
+        let mut source = None;
+        assert_eq!(source_arg("in.txt", &mut source), Ok(()));
+        assert_eq!(source, Some(Source::from_arg("in.txt")));
+        assert_eq!(
+            source_arg("extra", &mut source),
+            Err("unexpected argument 'extra'".to_string())
+        );
+        assert_eq!(
+            source_arg("--fast", &mut None),
+            Err("unknown option '--fast'".to_string())
+        );
+        assert_eq!(positive_arg("--runs", "3"), Ok(3));
+        for value in ["0", "-1", "x"].iter() {
+            assert_eq!(
+                positive_arg("--runs", value),
+                Err(format!(
+                    "invalid --runs '{}', expected a positive number",
+                    value
+                ))
+            );
+        }
+        let mut source = None;
+        source_arg("-", &mut source).unwrap();
+        assert_eq!(source, Some(Source::Stdin));
+    }
+
+    #[test]
+    fn test_run_subcommand() {
+        let parse = |args: &[String]| match args {
+            [arg] => arg.parse::<u32>().map_err(|e| e.to_string()),
+            _ => Err("expected a number".to_string()),
+        };
+        let run = |n: &u32| match n {
+            0 => Err(crate::Error::no_solution("zero")),
+            n => Ok(format!("{}\n", n)),
+        };
+        assert_eq!(run_subcommand(&args("7"), parse, run, "usage"), 0);
+        assert_eq!(run_subcommand(&args("0"), parse, run, "usage"), 1);
+        assert_eq!(run_subcommand(&args("x"), parse, run, "usage"), 2);
+    }
+
+    #[test]
+    fn test_table_format() {
+        assert_eq!(TableFormat::parse_arg("csv"), Ok(TableFormat::Csv));
+        assert_eq!(TableFormat::parse_arg("json"), Ok(TableFormat::Json));
+        assert!(TableFormat::parse_arg("text").is_err());
+        assert_eq!(csv_field("a b"), "a b");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+/// Position of the offending token: input file, 1-based line number and
+/// 1-based byte column. Parts that are not known are left empty.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Location {
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(file) = &self.file {
+            parts.push(file.clone());
+        }
+        if let Some(line) = self.line {
+            parts.push(format!("line {}", line));
+        }
+        if let Some(column) = self.column {
+            parts.push(format!("column {}", column));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl Location {
+    fn is_empty(&self) -> bool {
+        self.file.is_none() && self.line.is_none() && self.column.is_none()
+    }
+}
+
+/// Error type shared by all the day crates.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io {
+        file: Option<String>,
+        source: io::Error,
+    },
+    /// The input is malformed: unexpected character, missing token, bad number...
+    Parse { location: Location, message: String },
+    /// The input is well-formed but breaks a rule of the puzzle.
+    Validation { location: Location, message: String },
+    /// The input was read fine but has no answer.
+    NoSolution(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        Error::Parse {
+            location: Location::default(),
+            message: message.into(),
+        }
+    }
+
+    pub fn validation<S: Into<String>>(message: S) -> Error {
+        Error::Validation {
+            location: Location::default(),
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution<S: Into<String>>(message: S) -> Error {
+        Error::NoSolution(message.into())
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Parse { location, .. } | Error::Validation { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    fn location_mut(&mut self) -> Option<&mut Location> {
+        match self {
+            Error::Parse { location, .. } | Error::Validation { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Sets the line number unless an inner call already did.
+    pub fn at_line(mut self, line: usize) -> Error {
+        if let Some(location) = self.location_mut() {
+            location.line.get_or_insert(line);
+        }
+        self
+    }
+
+    /// Sets the byte column unless an inner call already did.
+    pub fn at_column(mut self, column: usize) -> Error {
+        if let Some(location) = self.location_mut() {
+            location.column.get_or_insert(column);
+        }
+        self
+    }
+
+    /// Sets the input file name unless it is already known.
+    pub fn in_file(mut self, name: &str) -> Error {
+        match &mut self {
+            Error::Io { file, .. } => {
+                file.get_or_insert_with(|| name.to_string());
+            }
+            _ => {
+                if let Some(location) = self.location_mut() {
+                    location.file.get_or_insert_with(|| name.to_string());
+                }
+            }
+        };
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io {
+                file: Some(file),
+                source,
+            } => write!(f, "{}: I/O error: {}", file, source),
+            Error::Io { file: None, source } => write!(f, "I/O error: {}", source),
+            Error::Parse { location, message } if location.is_empty() => {
+                write!(f, "parse error: {}", message)
+            }
+            Error::Parse { location, message } => {
+                write!(f, "{}: parse error: {}", location, message)
+            }
+            Error::Validation { location, message } if location.is_empty() => {
+                write!(f, "validation error: {}", message)
+            }
+            Error::Validation { location, message } => {
+                write!(f, "{}: validation error: {}", location, message)
+            }
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A problem in the input that does not prevent solving the puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    pub location: Location,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new<S: Into<String>>(message: S) -> Warning {
+        Warning {
+            location: Location::default(),
+            message: message.into(),
+        }
+    }
+
+    /// Sets the line number unless an inner call already did.
+    pub fn at_line(mut self, line: usize) -> Warning {
+        self.location.line.get_or_insert(line);
+        self
+    }
+
+    /// Sets the byte column unless an inner call already did.
+    pub fn at_column(mut self, column: usize) -> Warning {
+        self.location.column.get_or_insert(column);
+        self
+    }
+
+    /// Sets the input file name unless it is already known.
+    pub fn in_file(mut self, name: &str) -> Warning {
+        self.location.file.get_or_insert_with(|| name.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.location.is_empty() {
+            write!(f, "warning: {}", self.message)
+        } else {
+            write!(f, "{}: warning: {}", self.location, self.message)
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io {
+            file: None,
+            source: err,
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Error {
+        Error::parse(format!("invalid number: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::parse("unknown character")
+            .at_column(5)
+            .at_line(3)
+            .in_file("small.txt");
+        assert_eq!(
+            err.to_string(),
+            "small.txt, line 3, column 5: parse error: unknown character"
+        );
+        assert_eq!(
+            Error::validation("bad hgt").to_string(),
+            "validation error: bad hgt"
+        );
+        assert_eq!(
+            Error::no_solution("no empty seat").to_string(),
+            "no solution: no empty seat"
+        );
+    }
+
+    #[test]
+    fn test_warning() {
+        let warning = Warning::new("duplicate key").at_line(3).in_file("a.txt");
+        assert_eq!(warning.to_string(), "a.txt, line 3: warning: duplicate key");
+        assert_eq!(Warning::new("odd").to_string(), "warning: odd");
+    }
+
+    #[test]
+    fn test_inner_location_wins() {
+        let err = Error::parse("bad").at_line(2).at_line(7).at_column(4);
+        let loc = err.location().unwrap();
+        assert_eq!(loc.line, Some(2));
+        assert_eq!(loc.column, Some(4));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let err: Error = "x1".parse::<u32>().unwrap_err().into();
+        assert!(matches!(err, Error::Parse { .. }));
+        let err: Error = io::Error::new(io::ErrorKind::NotFound, "gone").into();
+        assert_eq!(
+            err.in_file("input.txt").to_string(),
+            "input.txt: I/O error: gone"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::error::{Error, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor};
+use std::path::PathBuf;
+
+/// Where the puzzle input is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    /// A file on disk.
+    File(PathBuf),
+    /// Standard input, selected with `-` on the command line.
+    Stdin,
+    /// An in-memory string, mostly useful in tests.
+    Text(String),
+}
+
+impl Source {
+    /// Builds a source from a command line argument: `-` means stdin,
+    /// anything else is a path.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Name of the source as shown in messages.
+    pub fn name(&self) -> String {
+        match self {
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".to_string(),
+            Source::Text(_) => "<text>".to_string(),
+        }
+    }
+
+    /// Opens the source and returns a lazy iterator over its lines.
+    pub fn lines(&self, options: ReadOptions) -> Result<Lines> {
+        let reader: Box<dyn BufRead> = match self {
+            Source::File(path) => match File::open(path) {
+                Ok(file) => Box::new(BufReader::new(file)),
+                Err(err) => return Err(Error::from(err).in_file(&self.name())),
+            },
+            Source::Stdin => Box::new(BufReader::new(io::stdin())),
+            Source::Text(text) => Box::new(Cursor::new(text.clone().into_bytes())),
+        };
+        Ok(Lines {
+            reader,
+            name: self.name(),
+            options,
+            number: 0,
+        })
+    }
+}
+
+/// How whitespace around each line is handled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trim {
+    /// Keep the line as it is.
+    None,
+    /// Remove trailing whitespace only, so byte columns stay unchanged.
+    End,
+    /// Remove leading and trailing whitespace.
+    Both,
+}
+
+/// What to do with lines that are empty after trimming.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmptyLines {
+    /// Keep them, e.g. when blank lines separate records.
+    Keep,
+    /// Drop them silently.
+    Skip,
+}
+
+/// Options applied to every line read from a [`Source`].
+///
+/// The default keeps lines untouched apart from CRLF normalisation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReadOptions {
+    pub trim: Trim,
+    pub empty_lines: EmptyLines,
+    /// Strip the `\r` of a `\r\n` line ending.
+    pub normalize_crlf: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions {
+            trim: Trim::None,
+            empty_lines: EmptyLines::Keep,
+            normalize_crlf: true,
+        }
+    }
+}
+
+impl ReadOptions {
+    pub fn trim(mut self, trim: Trim) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    pub fn skip_empty(mut self) -> Self {
+        self.empty_lines = EmptyLines::Skip;
+        self
+    }
+
+    pub fn keep_crlf(mut self) -> Self {
+        self.normalize_crlf = false;
+        self
+    }
+
+    // apply the options to a raw line (without its '\n'); None means the line is dropped
+    fn apply(&self, mut text: String) -> Option<String> {
+        if self.normalize_crlf && text.ends_with('\r') {
+            text.pop();
+        }
+        let text = match self.trim {
+            Trim::None => text,
+            Trim::End => text.trim_end().to_string(),
+            Trim::Both => text.trim().to_string(),
+        };
+        if self.empty_lines == EmptyLines::Skip && text.is_empty() {
+            return None;
+        }
+        Some(text)
+    }
+}
+
+/// A line of input together with its 1-based line number in the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub number: usize,
+    pub text: String,
+}
+
+/// Lazy iterator over the lines of a [`Source`], see [`Source::lines`].
+pub struct Lines {
+    reader: Box<dyn BufRead>,
+    name: String,
+    options: ReadOptions,
+    number: usize,
+}
+
+impl Iterator for Lines {
+    type Item = Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut buf = String::new();
+            match self.reader.read_line(&mut buf) {
+                Err(err) => {
+                    let err = Error::from(err).in_file(&self.name);
+                    return Some(Err(err.at_line(self.number + 1)));
+                }
+                Ok(0) => return None,
+                Ok(_) => (),
+            };
+            self.number += 1;
+            if buf.ends_with('\n') {
+                buf.pop();
+            }
+            if let Some(text) = self.options.apply(buf) {
+                return Some(Ok(Line {
+                    number: self.number,
+                    text,
+                }));
+            }
+        }
+    }
+}
+
+/// The whole input read into memory, for solutions that need several passes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    name: String,
+    lines: Vec<Line>,
+}
+
+impl Input {
+    /// Reads all lines of the source, failing on the first I/O error.
+    pub fn read(source: &Source, options: ReadOptions) -> Result<Input> {
+        let lines = source.lines(options)?.collect::<Result<Vec<Line>>>()?;
+        Ok(Input {
+            name: source.name(),
+            lines,
+        })
+    }
+
+    /// Builds an input from an in-memory string.
+    pub fn from_text(text: &str, options: ReadOptions) -> Input {
+        // reading from memory cannot fail
+        Input::read(&Source::Text(text.to_string()), options).unwrap()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// Iterates over the text of the lines, without line numbers.
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(|l| l.text.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// Splits `text` on whitespace like `str::split_whitespace`, also returning
+/// the 1-based byte column of every token for error reporting.
+pub fn tokens(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - text.as_ptr() as usize + 1, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str, options: ReadOptions) -> Vec<String> {
+        Input::from_text(text, options)
+            .texts()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("testdata/input.txt"),
+            Source::File(PathBuf::from("testdata/input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_default_options() {
+        let opts = ReadOptions::default();
+        assert_eq!(texts("a \r\n\r\n b\n", opts), vec!["a ", "", " b"]);
+        assert_eq!(texts("a\nb", opts), vec!["a", "b"]);
+        assert!(texts("", opts).is_empty());
+    }
+
+    #[test]
+    fn test_trim_and_skip() {
+        let opts = ReadOptions::default().trim(Trim::Both).skip_empty();
+        assert_eq!(texts(" 1721 \n\n  \n979\r\n", opts), vec!["1721", "979"]);
+        let opts = ReadOptions::default().trim(Trim::End);
+        assert_eq!(texts(" a  \n", opts), vec![" a"]);
+    }
+
+    #[test]
+    fn test_keep_crlf() {
+        let opts = ReadOptions::default().keep_crlf();
+        assert_eq!(texts("a\r\nb\r\n", opts), vec!["a\r", "b\r"]);
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let input = Input::from_text("a\n\nb\n", ReadOptions::default().skip_empty());
+        let numbers: Vec<usize> = input.lines().iter().map(|l| l.number).collect();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(input.name(), "<text>");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let source = Source::File(PathBuf::from("testdata/does-not-exist.txt"));
+        match Input::read(&source, ReadOptions::default()) {
+            Err(Error::Io { file, .. }) => {
+                assert_eq!(file.as_deref(), Some("testdata/does-not-exist.txt"))
+            }
+            _ => panic!("expected an I/O error"),
+        }
+    }
+
+    #[test]
+    fn test_tokens() {
+        let toks: Vec<(usize, &str)> = tokens("  ecl:gry  pid:860033327").collect();
+        assert_eq!(toks, vec![(3, "ecl:gry"), (12, "pid:860033327")]);
+    }
+}
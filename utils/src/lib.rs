@@ -1,11 +1,14 @@
-pub mod utils {
+//! Helpers shared by the Advent of Code 2020 day crates.
 
-}
+pub mod cli;
+pub mod error;
+pub mod input;
+pub mod report;
+pub mod solution;
+pub mod timing;
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+pub use error::{Error, Location, Result, Warning};
+pub use input::{EmptyLines, Input, Line, Lines, ReadOptions, Source, Trim};
+pub use report::{PartResult, Report};
+pub use solution::{solve, Alternative, Part, Runner, Solution};
+pub use timing::Stats;
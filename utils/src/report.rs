@@ -0,0 +1,147 @@
+use crate::error::{Error, Location, Warning};
+use crate::solution::Part;
+use crate::timing::Stats;
+use serde_json::{json, Value};
+
+/// Answer of one implementation of a part and the time it took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: Part,
+    /// Name of the alternative implementation, `None` for the main one.
+    pub name: Option<&'static str>,
+    pub answer: String,
+    pub stats: Stats,
+}
+
+/// Everything a run of a day produced: answers, timings and warnings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    /// Name of the input, see [`crate::Source::name`].
+    pub input: String,
+    pub parse: Stats,
+    /// One result per requested part, each followed by the results of its
+    /// alternative implementations when they were run.
+    pub results: Vec<PartResult>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Report {
+    /// Results of the main implementations, one per requested part.
+    pub fn answers(&self) -> impl Iterator<Item = &PartResult> {
+        self.results.iter().filter(|r| r.name.is_none())
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        self.answers()
+            .find(|r| r.part == part)
+            .map(|r| r.answer.as_str())
+    }
+
+    /// The report as a single JSON object:
+    /// `{"day", "input", "parse", "parts": [{"part", "implementation", "answer", "time"}], "warnings"}`.
+    /// Answers are strings so that large numbers survive any JSON parser.
+    pub fn to_json(&self) -> Value {
+        let parts: Vec<Value> = self
+            .results
+            .iter()
+            .map(|r| {
+                json!({
+                    "part": r.part.number(),
+                    "implementation": r.name,
+                    "answer": r.answer,
+                    "time": stats_json(&r.stats),
+                })
+            })
+            .collect();
+        let warnings: Vec<Value> = self
+            .warnings
+            .iter()
+            .map(|w| {
+                json!({
+                    "message": w.message,
+                    "location": location_json(&w.location),
+                })
+            })
+            .collect();
+        json!({
+            "day": self.day,
+            "input": self.input,
+            "parse": stats_json(&self.parse),
+            "parts": parts,
+            "warnings": warnings,
+        })
+    }
+}
+
+/// A failed run as a JSON object: `{"day", "input", "error": {"message", "location"}}`.
+pub fn error_json(day: u8, input: &str, err: &Error) -> Value {
+    json!({
+        "day": day,
+        "input": input,
+        "error": {
+            "message": err.to_string(),
+            "location": err.location().map(location_json),
+        },
+    })
+}
+
+fn stats_json(stats: &Stats) -> Value {
+    json!({
+        "runs": stats.runs,
+        "mean_ns": stats.mean.as_nanos() as u64,
+        "median_ns": stats.median.as_nanos() as u64,
+        "min_ns": stats.min.as_nanos() as u64,
+        "max_ns": stats.max.as_nanos() as u64,
+        "stddev_ns": stats.stddev.as_nanos() as u64,
+    })
+}
+
+fn location_json(location: &Location) -> Value {
+    json!({
+        "file": location.file,
+        "line": location.line,
+        "column": location.column,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_to_json() {
+        let stats = Stats::from_samples(&[Duration::from_micros(5)]);
+        let report = Report {
+            day: 4,
+            input: "input.txt".to_string(),
+            parse: stats.clone(),
+            results: vec![PartResult {
+                part: Part::Two,
+                name: None,
+                answer: "145".to_string(),
+                stats,
+            }],
+            warnings: vec![Warning::new("unknown key").at_line(7)],
+        };
+        let value = report.to_json();
+        assert_eq!(value["day"], 4);
+        assert_eq!(value["parse"]["mean_ns"], 5000);
+        assert_eq!(value["parts"][0]["part"], 2);
+        assert_eq!(value["parts"][0]["answer"], "145");
+        assert_eq!(value["parts"][0]["implementation"], Value::Null);
+        assert_eq!(value["warnings"][0]["location"]["line"], 7);
+        assert_eq!(value["warnings"][0]["location"]["file"], Value::Null);
+    }
+
+    #[test]
+    fn test_error_json() {
+        let err = Error::parse("bad").at_line(2).in_file("x.txt");
+        let value = error_json(5, "x.txt", &err);
+        assert_eq!(value["error"]["message"], "x.txt, line 2: parse error: bad");
+        assert_eq!(value["error"]["location"]["line"], 2);
+        let value = error_json(5, "x.txt", &Error::no_solution("none"));
+        assert_eq!(value["error"]["location"], Value::Null);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::error::{Error, Result, Warning};
+use crate::input::{Input, ReadOptions};
+use crate::report::{PartResult, Report};
+use crate::timing::measure;
+use std::fmt::{self, Display};
+
+/// A day's puzzle: the input is parsed once and then both parts are
+/// solved from the parsed form.
+pub trait Solution {
+    /// Day of the month, 1 to 25.
+    const DAY: u8;
+
+    /// The parsed input shared by both parts.
+    type Parsed;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    /// How the input lines are read before `parse` sees them.
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions::default()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Parsed>;
+
+    fn part_one(&self, parsed: &Self::Parsed) -> Result<Self::PartOne>;
+
+    fn part_two(&self, parsed: &Self::Parsed) -> Result<Self::PartTwo>;
+
+    /// Problems found in the input that did not prevent solving it.
+    fn warnings(&self, _parsed: &Self::Parsed) -> Vec<Warning> {
+        Vec::new()
+    }
+
+    /// Other implementations of the parts, timed next to the main ones.
+    fn alternatives(&self) -> Vec<Alternative<Self::Parsed>> {
+        Vec::new()
+    }
+}
+
+/// Another implementation of one of the parts, e.g. a slower reference
+/// version kept around for comparison.
+pub struct Alternative<P> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&P) -> Result<String>,
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    /// Parses a `--part` argument: `1`, `2` or `both`.
+    pub fn parse_arg(arg: &str) -> Option<Vec<Part>> {
+        match arg {
+            "1" | "one" => Some(vec![Part::One]),
+            "2" | "two" => Some(vec![Part::Two]),
+            "both" => Some(Part::BOTH.to_vec()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], implemented for every solution so
+/// that a runner can hold any day behind `dyn Runner`.
+pub trait Runner {
+    fn day(&self) -> u8;
+
+    /// Same as [`Solution::read_options`].
+    fn options(&self) -> ReadOptions;
+
+    /// Parses the input once and solves the requested parts in order.
+    /// Errors and warnings are tagged with the input name.
+    fn run(&self, input: &Input, parts: &[Part]) -> Result<Report>;
+
+    /// Like `run`, but repeats the parsing and every part `runs` times,
+    /// alternative implementations of the requested parts included.
+    fn time(&self, input: &Input, parts: &[Part], runs: usize) -> Result<Report>;
+}
+
+impl<S: Solution> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn options(&self) -> ReadOptions {
+        self.read_options()
+    }
+
+    fn run(&self, input: &Input, parts: &[Part]) -> Result<Report> {
+        report(self, input, parts, 1, false)
+    }
+
+    fn time(&self, input: &Input, parts: &[Part], runs: usize) -> Result<Report> {
+        report(self, input, parts, runs, true)
+    }
+}
+
+fn report<S: Solution>(
+    solution: &S,
+    input: &Input,
+    parts: &[Part],
+    runs: usize,
+    with_alternatives: bool,
+) -> Result<Report> {
+    let tag = |e: Error| e.in_file(input.name());
+    let (parsed, parse) = measure(runs, || solution.parse(input)).map_err(tag)?;
+    let alternatives = if with_alternatives {
+        solution.alternatives()
+    } else {
+        Vec::new()
+    };
+    let mut results = Vec::new();
+    for &part in parts.iter() {
+        let (answer, stats) = match part {
+            Part::One => measure(runs, || Ok(solution.part_one(&parsed)?.to_string())),
+            Part::Two => measure(runs, || Ok(solution.part_two(&parsed)?.to_string())),
+        }
+        .map_err(tag)?;
+        results.push(PartResult {
+            part,
+            name: None,
+            answer,
+            stats,
+        });
+        for alt in alternatives.iter().filter(|alt| alt.part == part) {
+            let (answer, stats) = measure(runs, || (alt.solve)(&parsed)).map_err(tag)?;
+            results.push(PartResult {
+                part,
+                name: Some(alt.name),
+                answer,
+                stats,
+            });
+        }
+    }
+    let warnings = solution
+        .warnings(&parsed)
+        .into_iter()
+        .map(|w| w.in_file(input.name()))
+        .collect();
+    Ok(Report {
+        day: S::DAY,
+        input: input.name().to_string(),
+        parse,
+        results,
+        warnings,
+    })
+}
+
+/// Parses the input and solves both parts, returning the answers as text.
+/// Errors are tagged with the input name.
+pub fn solve<S: Solution>(solution: &S, input: &Input) -> Result<(String, String)> {
+    let tag = |e: Error| e.in_file(input.name());
+    let parsed = solution.parse(input).map_err(tag)?;
+    let one = solution.part_one(&parsed).map_err(tag)?;
+    let two = solution.part_two(&parsed).map_err(tag)?;
+    Ok((one.to_string(), two.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Parsed = Vec<i64>;
+        type PartOne = i64;
+        type PartTwo = i64;
+
+        fn parse(&self, input: &Input) -> Result<Vec<i64>> {
+            let mut vec = Vec::new();
+            for line in input.lines() {
+                vec.push(
+                    line.text
+                        .parse()
+                        .map_err(|e| Error::from(e).at_line(line.number))?,
+                );
+            }
+            Ok(vec)
+        }
+
+        fn part_one(&self, vec: &Vec<i64>) -> Result<i64> {
+            Ok(vec.iter().sum())
+        }
+
+        fn part_two(&self, vec: &Vec<i64>) -> Result<i64> {
+            Ok(vec.iter().product())
+        }
+
+        fn warnings(&self, vec: &Vec<i64>) -> Vec<Warning> {
+            if vec.iter().sum::<i64>() % 2 == 1 {
+                vec![Warning::new("sum is odd")]
+            } else {
+                Vec::new()
+            }
+        }
+
+        fn alternatives(&self) -> Vec<Alternative<Vec<i64>>> {
+            vec![Alternative {
+                name: "reversed",
+                part: Part::Two,
+                solve: |vec| Ok(vec.iter().rev().product::<i64>().to_string()),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = Input::from_text("2\n3\n4\n", ReadOptions::default());
+        assert_eq!(
+            solve(&Sum, &input).unwrap(),
+            ("9".to_string(), "24".to_string())
+        );
+        let input = Input::from_text("2\nx\n", ReadOptions::default());
+        let err = solve(&Sum, &input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "<text>, line 2: parse error: invalid number: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_runner() {
+        let runner: &dyn Runner = &Sum;
+        let input = Input::from_text("2\n3\n4\n", runner.options());
+        let report = runner.run(&input, &[Part::Two]).unwrap();
+        assert_eq!((report.day, report.input.as_str()), (0, "<text>"));
+        assert_eq!(report.answer(Part::One), None);
+        assert_eq!(report.answer(Part::Two), Some("24"));
+        // alternatives only run when timing
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(
+            report.warnings,
+            vec![Warning::new("sum is odd").in_file("<text>")]
+        );
+        assert_eq!(Part::parse_arg("both"), Some(vec![Part::One, Part::Two]));
+        assert_eq!(Part::parse_arg("3"), None);
+    }
+
+    #[test]
+    fn test_time() {
+        let input = Input::from_text("2\n3\n4\n", ReadOptions::default());
+        let report = Sum.time(&input, &Part::BOTH, 3).unwrap();
+        assert_eq!(report.parse.runs, 3);
+        let parts: Vec<(Part, Option<&str>, &str)> = report
+            .results
+            .iter()
+            .map(|t| (t.part, t.name, t.answer.as_str()))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                (Part::One, None, "9"),
+                (Part::Two, None, "24"),
+                (Part::Two, Some("reversed"), "24"),
+            ]
+        );
+    }
+}
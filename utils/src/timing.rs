@@ -0,0 +1,93 @@
+use crate::error::Result;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Summary of the durations of repeated runs of the same code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    /// Standard deviation around the mean.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of a non-empty list of durations.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / n as f64;
+        Stats {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            max: sorted[n - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.2?} ± {:.2?} (min {:.2?}, median {:.2?}, max {:.2?}, {} runs)",
+            self.mean, self.stddev, self.min, self.median, self.max, self.runs
+        )
+    }
+}
+
+/// Runs `f` `runs` times (at least once), returning the result of the last
+/// run and the statistics of all of them. Stops at the first error.
+pub fn measure<T, F: FnMut() -> Result<T>>(runs: usize, mut f: F) -> Result<(T, Stats)> {
+    let mut samples = Vec::with_capacity(runs.max(1));
+    loop {
+        let start = Instant::now();
+        let value = f()?;
+        samples.push(start.elapsed());
+        if samples.len() >= runs {
+            return Ok((value, Stats::from_samples(&samples)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(stats.runs, 4);
+        assert_eq!((stats.min, stats.max), (ms(1), ms(4)));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (value, stats) = measure(3, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!((value, stats.runs), (3, 3));
+        assert_eq!(measure(0, || Ok(())).unwrap().1.runs, 1);
+    }
+}